@@ -1,9 +1,14 @@
 mod decode_from_expr;
+mod decode_from_slice_expr;
+mod decode_in_place_expr;
 
 use quote::quote;
-use syn::{parse_macro_input, parse_quote, DeriveInput};
+use syn::{parse_macro_input, parse_quote, DeriveInput, GenericParam, LifetimeDef};
 
-use self::decode_from_expr::DecodeFromExpr;
+use self::{
+    decode_from_expr::DecodeFromExpr, decode_from_slice_expr::DecodeFromSliceExpr,
+    decode_in_place_expr::DecodeInPlaceExpr,
+};
 use crate::{context::Context, util::add_trait_bounds};
 
 pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
@@ -13,8 +18,9 @@ pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     // Used in the quasi-quotation below as `#name`.
     let name = input.ident;
 
-    // Add a bound `T: Decode` to every type parameter T.
+    // Add a bound `T: Decode + Send` to every type parameter T (`Send` is required for returned future to be `Send`).
     let generics = add_trait_bounds(input.generics, parse_quote!(Decode));
+    let generics = add_trait_bounds(generics, parse_quote!(Send));
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     // Create context for generating expressions
@@ -23,10 +29,34 @@ pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     // Generate expression for decoding value from reader.
     let decode_from = context.decode_from_expr();
 
+    // Generate expression for synchronously decoding value from a byte slice.
+    let decode_from_slice = context.decode_from_slice_expr();
+
+    // Generate expression for decoding value from `Bytes` (only compiled when `bytes` feature of `nimble` is enabled).
+    let decode_from_bytes = context.decode_from_bytes_expr();
+
+    // Generate `decode_in_place()` method for structs (enums use default implementation which replaces the value).
+    //
+    // `Self: Send` bound is higher-ranked so that it is not rejected as trivially false for non-`Send` structs
+    // (whose `decode_in_place()` can never be called).
+    let decode_in_place = context.decode_in_place_expr().map(|decode_in_place| {
+        quote! {
+            async fn decode_in_place<R>(&mut self, config: &nimble::Config, mut reader: R) -> nimble::Result<()>
+            where
+                for<'__nimble> Self: Send,
+                R: nimble::io::Read + Unpin + Send,
+            {
+                #[allow(unused_imports)]
+                use nimble::__private::{DecodeFieldInPlace as _, DecodeFieldReplace as _};
+
+                #decode_in_place
+            }
+        }
+    });
+
     // Build the output, possibly using quasi-quotation
     let expanded = quote! {
         // The generated impl.
-        #[nimble::async_trait]
         impl #impl_generics Decode for #name #ty_generics #where_clause {
             async fn decode_from<R>(config: &nimble::Config, mut reader: R) -> nimble::Result<Self>
             where
@@ -34,6 +64,92 @@ pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
             {
                 #decode_from
             }
+
+            fn decode_from_slice(config: &nimble::Config, bytes: &mut &[u8]) -> nimble::Result<Self> {
+                #decode_from_slice
+            }
+
+            #decode_in_place
+
+            nimble::__cfg_bytes! {
+                fn decode_from_bytes(config: &nimble::Config, bytes: &mut nimble::__private::Bytes) -> nimble::Result<Self> {
+                    #decode_from_bytes
+                }
+            }
+        }
+    };
+
+    // Hand the output tokens back to the compiler
+    proc_macro::TokenStream::from(expanded)
+}
+
+pub fn derive_local(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    // Parse the input tokens into a syntax tree
+    let mut input = parse_macro_input!(input as DeriveInput);
+
+    // Used in the quasi-quotation below as `#name`.
+    let name = input.ident;
+
+    // Add a bound `T: LocalDecode` to every type parameter T.
+    let generics = add_trait_bounds(input.generics, parse_quote!(nimble::LocalDecode));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    // Create context for generating expressions
+    let context = Context::new(&name, &mut input.data);
+
+    // Generate expression for decoding value from reader.
+    let decode_from_local = context.decode_from_local_expr();
+
+    // Build the output, possibly using quasi-quotation
+    let expanded = quote! {
+        // The generated impl.
+        impl #impl_generics nimble::LocalDecode for #name #ty_generics #where_clause {
+            async fn decode_from_local<R>(config: &nimble::Config, mut reader: R) -> nimble::Result<Self>
+            where
+                R: nimble::io::Read + Unpin,
+            {
+                #decode_from_local
+            }
+        }
+    };
+
+    // Hand the output tokens back to the compiler
+    proc_macro::TokenStream::from(expanded)
+}
+
+pub fn derive_borrowed(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    // Parse the input tokens into a syntax tree
+    let mut input = parse_macro_input!(input as DeriveInput);
+
+    // Used in the quasi-quotation below as `#name`.
+    let name = input.ident;
+
+    // Add a bound `T: DecodeBorrowed<'de>` to every type parameter T.
+    let generics = add_trait_bounds(input.generics, parse_quote!(nimble::DecodeBorrowed<'de>));
+    let (_, ty_generics, where_clause) = generics.split_for_impl();
+
+    // Add `'de` lifetime (which outlives all the lifetimes of type) to generics of impl.
+    let mut de: LifetimeDef = parse_quote!('de);
+    de.bounds
+        .extend(generics.lifetimes().map(|def| def.lifetime.clone()));
+
+    let mut impl_generics = generics.clone();
+    impl_generics.params.insert(0, GenericParam::Lifetime(de));
+    let (impl_generics, _, _) = impl_generics.split_for_impl();
+
+    // Create context for generating expressions
+    let context = Context::new(&name, &mut input.data);
+
+    // Generate expression for decoding value from a byte slice (borrowing from it where possible).
+    let decode_borrowed = context.decode_borrowed_expr();
+
+    // Build the output, possibly using quasi-quotation
+    let expanded = quote! {
+        // The generated impl.
+        impl #impl_generics nimble::DecodeBorrowed<'de> for #name #ty_generics #where_clause {
+            fn decode_borrowed(config: &nimble::Config, bytes: &mut &'de [u8]) -> nimble::Result<Self> {
+                #decode_borrowed
+            }
         }
     };
 
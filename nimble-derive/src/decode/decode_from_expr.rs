@@ -10,43 +10,70 @@ use crate::{
 };
 
 pub trait DecodeFromExpr {
+    /// Returns expression that goes in `Decode::decode_from()` method
     fn decode_from_expr(&self) -> TokenStream;
+
+    /// Returns expression that goes in `LocalDecode::decode_from_local()` method
+    fn decode_from_local_expr(&self) -> TokenStream;
 }
 
 impl<'a> DecodeFromExpr for Context<'a> {
     fn decode_from_expr(&self) -> TokenStream {
-        let name = &self.name;
-
-        match &self.expr_type {
-            ExprType::Struct {
-                ref fields_type,
-                ref fields,
-            } => decode_bytes_expr(name, *fields_type, fields.clone()),
-            ExprType::Enum { ref variants } => {
-                let match_exprs = variants
-                    .clone()
-                    .enumerate()
-                    .map(|(i, variant)| -> TokenStream {
-                        let variant_name = variant.get_name();
-                        let fields_type = variant.fields.get_type();
-                        let fields = variant.fields.iter_fields();
-
-                        let decode_bytes_expr =
-                            decode_bytes_expr(&quote!(#name :: #variant_name), fields_type, fields);
-                        let index = u128::try_from(i).expect("Failed to convert usize to u128. Log an issue on nimble's GitHub repository with backtrace.");
-
-                        quote_spanned! {variant.span()=>
-                            #index => #decode_bytes_expr
-                        }
-                    });
-
-                quote! {
-                    let option = u128::from(<nimble::VarInt>::decode_from(config, &mut reader).await?);
-
-                    match option {
-                        #(#match_exprs,)*
-                        _ => Err(nimble::Error::InvalidEnumVariant(option.into())),
+        decode_from_expr_with(self, &quote!(Decode), &quote!(decode_from))
+    }
+
+    fn decode_from_local_expr(&self) -> TokenStream {
+        decode_from_expr_with(
+            self,
+            &quote!(nimble::LocalDecode),
+            &quote!(decode_from_local),
+        )
+    }
+}
+
+/// Returns expression to decode a struct or enum using `decode` method of `decode_trait` (`Decode::decode_from` or
+/// `LocalDecode::decode_from_local`) for decoding enum variant index and every field
+fn decode_from_expr_with(
+    context: &Context<'_>,
+    decode_trait: &TokenStream,
+    decode: &TokenStream,
+) -> TokenStream {
+    let name = &context.name;
+
+    match &context.expr_type {
+        ExprType::Struct {
+            ref fields_type,
+            ref fields,
+        } => decode_bytes_expr(name, *fields_type, fields.clone(), decode_trait, decode),
+        ExprType::Enum { ref variants } => {
+            let match_exprs = variants
+                .clone()
+                .enumerate()
+                .map(|(i, variant)| -> TokenStream {
+                    let variant_name = variant.get_name();
+                    let fields_type = variant.fields.get_type();
+                    let fields = variant.fields.iter_fields();
+
+                    let decode_bytes_expr = decode_bytes_expr(
+                        &quote!(#name :: #variant_name),
+                        fields_type,
+                        fields,
+                        decode_trait,
+                        decode,
+                    );
+                    let index = u128::try_from(i).expect("Failed to convert usize to u128. Log an issue on nimble's GitHub repository with backtrace.");
+
+                    quote_spanned! {variant.span()=>
+                        #index => #decode_bytes_expr
                     }
+                });
+
+            quote! {
+                let option = u128::from(<nimble::VarInt as #decode_trait>::#decode(config, &mut reader).await?);
+
+                match option {
+                    #(#match_exprs,)*
+                    _ => Err(nimble::Error::InvalidEnumVariant(option.into())),
                 }
             }
         }
@@ -60,6 +87,7 @@ impl<'a> DecodeFromExpr for Context<'a> {
 /// - `name`: Name of struct/enum
 /// - `fields_type`: Type of fields (`Named`, `Unnamed` or `Unit`)
 /// - `fields`: Iterator over all the fields of struct or enum variant
+/// - `decode_trait`, `decode`: Trait and method used to decode each field (for example, `Decode` and `decode_from`)
 ///
 /// # Example
 ///
@@ -76,14 +104,16 @@ impl<'a> DecodeFromExpr for Context<'a> {
 ///
 /// ```ignore
 /// Ok(MyStruct {
-///     a: <u8>::decode_from(config, &mut reader).await?,
-///     b: <u16>::decode_from(config, &mut reader).await?,
+///     a: <u8 as Decode>::decode_from(config, &mut reader).await?,
+///     b: <u16 as Decode>::decode_from(config, &mut reader).await?,
 /// })
 /// ```
 fn decode_bytes_expr<T: ToTokens>(
     name: &T,
     fields_type: FieldsType,
     fields: Iter<'_, Field>,
+    decode_trait: &TokenStream,
+    decode: &TokenStream,
 ) -> TokenStream {
     let field_exprs = fields.map(|f| -> TokenStream {
         let field_type = &f.get_type();
@@ -95,12 +125,12 @@ fn decode_bytes_expr<T: ToTokens>(
                     .expect("Named fields are expected to have identifiers");
 
                 quote_spanned! {f.span()=>
-                    #field_name: <#field_type>::decode_from(config, &mut reader).await?
+                    #field_name: <#field_type as #decode_trait>::#decode(config, &mut reader).await?
                 }
             }
             FieldsType::Unnamed => {
                 quote_spanned! {f.span()=>
-                    <#field_type>::decode_from(config, &mut reader).await?
+                    <#field_type as #decode_trait>::#decode(config, &mut reader).await?
                 }
             }
             FieldsType::Unit => {
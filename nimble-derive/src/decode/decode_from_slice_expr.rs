@@ -0,0 +1,175 @@
+use core::convert::TryFrom;
+
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned, ToTokens};
+use syn::{punctuated::Iter, spanned::Spanned, Field};
+
+use crate::{
+    context::{Context, ExprType},
+    util::{FieldExt, FieldsExt, FieldsType, VariantExt},
+};
+
+pub trait DecodeFromSliceExpr {
+    /// Returns expression that goes in `Decode::decode_from_slice()` method
+    fn decode_from_slice_expr(&self) -> TokenStream;
+
+    /// Returns expression that goes in `Decode::decode_from_bytes()` method
+    fn decode_from_bytes_expr(&self) -> TokenStream;
+
+    /// Returns expression that goes in `DecodeBorrowed::decode_borrowed()` method
+    fn decode_borrowed_expr(&self) -> TokenStream;
+}
+
+impl<'a> DecodeFromSliceExpr for Context<'a> {
+    fn decode_from_slice_expr(&self) -> TokenStream {
+        slice_decoding_expr(self, &quote!(Decode), &quote!(decode_from_slice))
+    }
+
+    fn decode_from_bytes_expr(&self) -> TokenStream {
+        slice_decoding_expr(self, &quote!(Decode), &quote!(decode_from_bytes))
+    }
+
+    fn decode_borrowed_expr(&self) -> TokenStream {
+        slice_decoding_expr(
+            self,
+            &quote!(nimble::DecodeBorrowed<'de>),
+            &quote!(decode_borrowed),
+        )
+    }
+}
+
+/// Returns expression to decode struct/enum from a slice, where each field is decoded using `decode_method` of
+/// `decode_trait`
+fn slice_decoding_expr(
+    context: &Context<'_>,
+    decode_trait: &TokenStream,
+    decode_method: &TokenStream,
+) -> TokenStream {
+    let name = &context.name;
+
+    match &context.expr_type {
+        ExprType::Struct {
+            ref fields_type,
+            ref fields,
+        } => decode_bytes_expr(
+            name,
+            *fields_type,
+            fields.clone(),
+            decode_trait,
+            decode_method,
+        ),
+        ExprType::Enum { ref variants } => {
+            let match_exprs = variants
+                    .clone()
+                    .enumerate()
+                    .map(|(i, variant)| -> TokenStream {
+                        let variant_name = variant.get_name();
+                        let fields_type = variant.fields.get_type();
+                        let fields = variant.fields.iter_fields();
+
+                        let decode_bytes_expr = decode_bytes_expr(
+                            &quote!(#name :: #variant_name),
+                            fields_type,
+                            fields,
+                            decode_trait,
+                            decode_method,
+                        );
+                        let index = u128::try_from(i).expect("Failed to convert usize to u128. Log an issue on nimble's GitHub repository with backtrace.");
+
+                        quote_spanned! {variant.span()=>
+                            #index => #decode_bytes_expr
+                        }
+                    });
+
+            quote! {
+                let option = u128::from(<nimble::VarInt as #decode_trait>::#decode_method(config, bytes)?);
+
+                match option {
+                    #(#match_exprs,)*
+                    _ => Err(nimble::Error::InvalidEnumVariant(option.into())),
+                }
+            }
+        }
+    }
+}
+
+/// Returns expression to synchronously decode bytes from a slice into fields
+///
+/// # Arguments
+///
+/// - `name`: Name of struct/enum
+/// - `fields_type`: Type of fields (`Named`, `Unnamed` or `Unit`)
+/// - `fields`: Iterator over all the fields of struct or enum variant
+/// - `decode_trait`: Trait used to decode each field (for example, `Decode`)
+/// - `decode_method`: Method of `decode_trait` used to decode each field (for example, `decode_from_slice`)
+///
+/// # Example
+///
+/// For below struct:
+///
+/// ```rust,ignore
+/// struct MyStruct {
+///     a: u8,
+///     b: u16,
+/// }
+/// ```
+///
+/// This function will return:
+///
+/// ```ignore
+/// Ok(MyStruct {
+///     a: <u8 as Decode>::decode_from_slice(config, bytes)?,
+///     b: <u16 as Decode>::decode_from_slice(config, bytes)?,
+/// })
+/// ```
+///
+/// assuming `decode_trait = Decode` and `decode_method = decode_from_slice`.
+fn decode_bytes_expr<T: ToTokens>(
+    name: &T,
+    fields_type: FieldsType,
+    fields: Iter<'_, Field>,
+    decode_trait: &TokenStream,
+    decode_method: &TokenStream,
+) -> TokenStream {
+    let field_exprs = fields.map(|f| -> TokenStream {
+        let field_type = &f.get_type();
+
+        match fields_type {
+            FieldsType::Named => {
+                let field_name = &f
+                    .get_name()
+                    .expect("Named fields are expected to have identifiers");
+
+                quote_spanned! {f.span()=>
+                    #field_name: <#field_type as #decode_trait>::#decode_method(config, bytes)?
+                }
+            }
+            FieldsType::Unnamed => {
+                quote_spanned! {f.span()=>
+                    <#field_type as #decode_trait>::#decode_method(config, bytes)?
+                }
+            }
+            FieldsType::Unit => {
+                panic!("Unit structs or enum variants are not expected to have fields")
+            }
+        }
+    });
+
+    match fields_type {
+        FieldsType::Named => {
+            quote! {
+                Ok(#name {
+                    #(#field_exprs,)*
+                })
+            }
+        }
+        FieldsType::Unnamed => {
+            quote! {
+                Ok(#name (
+                    #(#field_exprs,)*
+                ))
+            }
+        }
+        FieldsType::Unit => quote!(Ok(#name)),
+    }
+}
@@ -0,0 +1,70 @@
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::{spanned::Spanned, Index};
+
+use crate::{
+    context::{Context, ExprType},
+    util::FieldExt,
+};
+
+pub trait DecodeInPlaceExpr {
+    /// Returns expression that goes in `Decode::decode_in_place()` method (`None` for enums, which use default
+    /// implementation)
+    fn decode_in_place_expr(&self) -> Option<TokenStream>;
+}
+
+impl<'a> DecodeInPlaceExpr for Context<'a> {
+    /// For below struct:
+    ///
+    /// ```rust,ignore
+    /// struct MyStruct {
+    ///     a: u8,
+    ///     b: Vec<u16>,
+    /// }
+    /// ```
+    ///
+    /// This function will return:
+    ///
+    /// ```ignore
+    /// if let Some(value) = (&FieldProbe::of(&self.a)).decode_field(&mut self.a, config, &mut reader).await? {
+    ///     self.a = value;
+    /// }
+    /// if let Some(value) = (&FieldProbe::of(&self.b)).decode_field(&mut self.b, config, &mut reader).await? {
+    ///     self.b = value;
+    /// }
+    /// Ok(())
+    /// ```
+    ///
+    /// `decode_field()` decodes `Send` fields in place and returns a new value for other fields (which do not support
+    /// `decode_in_place()`).
+    fn decode_in_place_expr(&self) -> Option<TokenStream> {
+        match &self.expr_type {
+            ExprType::Struct { ref fields, .. } => {
+                let field_exprs = fields.clone().enumerate().map(|(i, f)| {
+                    let field = match f.get_name() {
+                        Some(field_name) => quote!(#field_name),
+                        None => {
+                            let index = Index::from(i);
+                            quote!(#index)
+                        }
+                    };
+
+                    quote_spanned! {f.span()=>
+                        if let Some(value) = (&nimble::__private::FieldProbe::of(&self.#field))
+                            .decode_field(&mut self.#field, config, &mut reader)
+                            .await?
+                        {
+                            self.#field = value;
+                        }
+                    }
+                });
+
+                Some(quote! {
+                    #(#field_exprs)*
+                    Ok(())
+                })
+            }
+            ExprType::Enum { .. } => None,
+        }
+    }
+}
@@ -1,11 +1,16 @@
 mod encode_to_expr;
+mod encode_to_vec_expr;
+mod fixed_size_expr;
 mod size_expr;
 
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::{parse_macro_input, parse_quote, DeriveInput};
 
-use self::{encode_to_expr::EncodeToExpr, size_expr::SizeExpr};
+use self::{
+    encode_to_expr::EncodeToExpr, encode_to_vec_expr::EncodeToVecExpr,
+    fixed_size_expr::FixedSizeExpr, size_expr::SizeExpr,
+};
 use crate::{context::Context, util::add_trait_bounds};
 
 pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
@@ -15,24 +20,32 @@ pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     // Used in the quasi-quotation below as `#name`.
     let name = input.ident;
 
-    // Add a bound `T: Encode` to every type parameter T.
+    // Add a bound `T: Encode + Sync` to every type parameter T (`Sync` is required for returned future to be `Send`).
     let generics = add_trait_bounds(input.generics, parse_quote!(Encode));
+    let generics = add_trait_bounds(generics, parse_quote!(Sync));
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     // Create context for generating expressions
     let context = Context::new(&name, &mut input.data);
 
+    // Generate constant declaring fixed size of encoded byte array for structs (enums use default value).
+    let fixed_size = context.fixed_size_expr();
+
     // Generate an expression for calculating size of encoded byte array.
     let size: TokenStream = context.size_expr();
 
     // Generate expression for encoding value to byte array and writing it to writer.
     let encode_to = context.encode_to_expr();
 
+    // Generate expression for synchronously encoding value to byte array and appending it to a `Vec`.
+    let encode_to_vec = context.encode_to_vec_expr();
+
     // Build the output, possibly using quasi-quotation
     let expanded = quote! {
         // The generated impl.
-        #[nimble::async_trait]
         impl #impl_generics Encode for #name #ty_generics #where_clause {
+            #fixed_size
+
             fn size(&self) -> usize {
                 #size
             }
@@ -43,6 +56,44 @@ pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
             {
                 #encode_to
             }
+
+            fn encode_to_vec(&self, config: &nimble::Config, buf: &mut nimble::__private::Vec<u8>) -> usize {
+                #encode_to_vec
+            }
+        }
+    };
+
+    // Hand the output tokens back to the compiler
+    proc_macro::TokenStream::from(expanded)
+}
+
+pub fn derive_local(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    // Parse the input tokens into a syntax tree
+    let mut input = parse_macro_input!(input as DeriveInput);
+
+    // Used in the quasi-quotation below as `#name`.
+    let name = input.ident;
+
+    // Add a bound `T: LocalEncode` to every type parameter T.
+    let generics = add_trait_bounds(input.generics, parse_quote!(nimble::LocalEncode));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    // Create context for generating expressions
+    let context = Context::new(&name, &mut input.data);
+
+    // Generate expression for encoding value to byte array and writing it to writer.
+    let encode_to_local = context.encode_to_local_expr();
+
+    // Build the output, possibly using quasi-quotation
+    let expanded = quote! {
+        // The generated impl.
+        impl #impl_generics nimble::LocalEncode for #name #ty_generics #where_clause {
+            async fn encode_to_local<W>(&self, config: &nimble::Config, mut writer: W) -> nimble::Result<usize>
+            where
+                W: nimble::io::Write + Unpin,
+            {
+                #encode_to_local
+            }
         }
     };
 
@@ -8,45 +8,60 @@ use crate::{
 };
 
 pub trait EncodeToExpr {
+    /// Returns expression that goes in `Encode::encode_to()` method
     fn encode_to_expr(&self) -> TokenStream;
+
+    /// Returns expression that goes in `LocalEncode::encode_to_local()` method
+    fn encode_to_local_expr(&self) -> TokenStream;
 }
 
 impl<'a> EncodeToExpr for Context<'a> {
     fn encode_to_expr(&self) -> TokenStream {
-        let name = &self.name;
-        let field_prefix = &self.field_prefix;
+        encode_to_expr_with(self, &quote!(Encode::encode_to))
+    }
 
-        match &self.expr_type {
-            ExprType::Struct { ref fields, .. } => {
-                bytes_encoding_expr(fields.clone(), field_prefix, None)
-            }
-            ExprType::Enum { ref variants } => {
-                let match_exprs = variants
-                    .clone()
-                    .enumerate()
-                    .map(|(i, variant)| -> TokenStream {
-                        let span = variant.span();
-                        let variant_name = variant.get_name();
-                        let fields_type = variant.fields.get_type();
-                        let fields = &variant.fields;
-                        let pattern_matching =
-                            get_variant_pattern_match_expr(fields.iter_fields(), fields_type, true);
-                        let variant_index = i as u128;
-                        let bytes_encoding = bytes_encoding_expr(
-                            fields.iter_fields(),
-                            field_prefix,
-                            Some(quote! {Encode::encode_to(& nimble::VarInt::from( #variant_index ), config, &mut writer).await?}),
-                        );
+    fn encode_to_local_expr(&self) -> TokenStream {
+        encode_to_expr_with(self, &quote!(nimble::LocalEncode::encode_to_local))
+    }
+}
+
+/// Returns expression to encode a struct or enum using `encode` function (`Encode::encode_to` or
+/// `LocalEncode::encode_to_local`) for encoding enum variant index and every field
+fn encode_to_expr_with(context: &Context<'_>, encode: &TokenStream) -> TokenStream {
+    let name = &context.name;
+    let field_prefix = &context.field_prefix;
 
-                        quote_spanned! {span=>
-                            #name :: #variant_name #pattern_matching => #bytes_encoding
-                        }
-                    });
+    match &context.expr_type {
+        ExprType::Struct { ref fields, .. } => {
+            bytes_encoding_expr(fields.clone(), field_prefix, encode, None)
+        }
+        ExprType::Enum { ref variants } => {
+            let match_exprs = variants
+                .clone()
+                .enumerate()
+                .map(|(i, variant)| -> TokenStream {
+                    let span = variant.span();
+                    let variant_name = variant.get_name();
+                    let fields_type = variant.fields.get_type();
+                    let fields = &variant.fields;
+                    let pattern_matching =
+                        get_variant_pattern_match_expr(fields.iter_fields(), fields_type, true);
+                    let variant_index = i as u128;
+                    let bytes_encoding = bytes_encoding_expr(
+                        fields.iter_fields(),
+                        field_prefix,
+                        encode,
+                        Some(quote! {#encode(& nimble::VarInt::from( #variant_index ), config, &mut writer).await?}),
+                    );
 
-                quote! {
-                    match self {
-                        #(#match_exprs,)*
+                    quote_spanned! {span=>
+                        #name :: #variant_name #pattern_matching => #bytes_encoding
                     }
+                });
+
+            quote! {
+                match self {
+                    #(#match_exprs,)*
                 }
             }
         }
@@ -59,6 +74,7 @@ impl<'a> EncodeToExpr for Context<'a> {
 ///
 /// - `fields`: An iterator over all the fields
 /// - `field_prefix`: Prefix to apply before accessing each field (for example, `&self.` is a field prefix for accessing struct fields)
+/// - `encode`: Function used to encode each field (for example, `Encode::encode_to`)
 /// - `base_expr`: Base encoding expression, if any (this expression is added to encoding expression)
 ///
 /// # Example
@@ -78,10 +94,11 @@ impl<'a> EncodeToExpr for Context<'a> {
 /// Ok(0 + Encode::encode_to(&self.a, config, &mut writer).await? + Encode::encode_to(&self.b, config, &mut writer).await?)
 /// ```
 ///
-/// assuming `field_prefix = &self.` and `base_expr = None`.
+/// assuming `field_prefix = &self.`, `encode = Encode::encode_to` and `base_expr = None`.
 fn bytes_encoding_expr(
     fields: Iter<'_, Field>,
     field_prefix: &TokenStream,
+    encode: &TokenStream,
     base_expr: Option<TokenStream>,
 ) -> TokenStream {
     let recurse = fields.enumerate().map(|(i, f)| {
@@ -89,12 +106,12 @@ fn bytes_encoding_expr(
 
         match field_name {
             Some(field_name) => quote_spanned! {f.span()=>
-                Encode::encode_to(#field_prefix #field_name, config, &mut writer).await?
+                #encode(#field_prefix #field_name, config, &mut writer).await?
             },
             None => {
                 let index = Index::from(i);
                 quote_spanned! {f.span()=>
-                    Encode::encode_to(#field_prefix #index, config, &mut writer).await?
+                    #encode(#field_prefix #index, config, &mut writer).await?
                 }
             }
         }
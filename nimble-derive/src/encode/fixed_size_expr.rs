@@ -0,0 +1,35 @@
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+
+use crate::{
+    context::{Context, ExprType},
+    util::FieldExt,
+};
+
+pub trait FixedSizeExpr {
+    /// Returns constant expression that goes in `Encode::FIXED_SIZE` (`None` for enums, which use the default value)
+    fn fixed_size_expr(&self) -> Option<TokenStream>;
+}
+
+impl<'a> FixedSizeExpr for Context<'a> {
+    fn fixed_size_expr(&self) -> Option<TokenStream> {
+        match &self.expr_type {
+            ExprType::Struct { ref fields, .. } => {
+                // Struct has a fixed size only if all of its fields have a fixed size
+                let fixed_size = fields.clone().fold(quote!(Some(0)), |fixed_size, f| {
+                    let field_type = f.get_type();
+
+                    quote_spanned! {f.span()=>
+                        nimble::__private::sum_fixed_sizes(#fixed_size, <#field_type as Encode>::FIXED_SIZE)
+                    }
+                });
+
+                Some(quote! {
+                    const FIXED_SIZE: Option<usize> = #fixed_size;
+                })
+            }
+            ExprType::Enum { .. } => None,
+        }
+    }
+}
@@ -18,7 +18,7 @@
 //! `encode()` will serialize this into `Vec` of size `3` (which is the sum of sizes of `u8` and `u16`).
 //!
 //! Similarly, for types which can have dynamic size (`Vec`, `String`, etc.), `encode()` prepends the size of encoded value
-//! as `u64`.
+//! as `VarInt`.
 //!
 //! ## Usage
 //!
@@ -26,14 +26,7 @@
 //!
 //! ```toml
 //! [dependencies]
-//! nimble = { version = "0.2", features = ["derive"] }
-//! ```
-//!
-//! Or, if you are in an environment based on `tokio`, use:
-//!
-//! ```toml
-//! [dependencies]
-//! nimble = { version = "0.2", features = ["derive", "tokio"] }
+//! nimble = { version = "0.3", features = ["derive"] }
 //! ```
 //!
 //! For encoding and decoding, any type must implement two traits provided by this crate, i.e., `Encode` and `Decode`. For
@@ -50,22 +43,17 @@
 //! ```
 //!
 //! Now you can use `encode()` and `decode()` functions to encode and decode values of `MyStruct`. In addition to this, you
-//! can also use `MyStruct::encode_to()` function to encode values directly to a type implementing `AsyncWrite` and
-//! `MyStruct::decode_from()` function to decode values directly from a type implementing `AsyncRead`.
-//!
-//! > Note: Most of the functions exposed by this crate are `async` functions and returns `Future` values. So, you'll need
-//! an executor to drive the `Future` returned from these functions. `async-std` and `tokio` are two popular options.
+//! can also use `MyStruct::encode_to()` function to encode values directly to a type implementing `nimble::io::Write` and
+//! `MyStruct::decode_from()` function to decode values directly from a type implementing `nimble::io::Read`.
 //!
 //! ### Features
 //!
-//! - `tokio`: Select this feature when you are using `tokio`'s executor to drive `Future` values returned by functions in
-//!   this crate. This implements `Encode` and `Decode` using `tokio`'s `AsyncRead`/`AsyncWrite` traits.
-//!   - **Disabled** by default.
-//! - `derive`: Enables derive macros for implementing `Encode` and `Decode` traits.
-//!   - **Disabled** by default.
+//! This crate is used through `nimble`'s `derive` feature, which enables derive macros for implementing `Encode`,
+//! `Decode`, `LocalEncode`, `LocalDecode`, `DecodeBorrowed` and `MaxSize` traits.
 mod context;
 mod decode;
 mod encode;
+mod max_size;
 mod util;
 
 #[proc_macro_derive(Encode)]
@@ -79,3 +67,27 @@ pub fn derive_encode(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 pub fn derive_decode(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     decode::derive(input)
 }
+
+#[proc_macro_derive(LocalEncode)]
+/// Derive macro to implement `LocalEncode` trait
+pub fn derive_local_encode(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    encode::derive_local(input)
+}
+
+#[proc_macro_derive(LocalDecode)]
+/// Derive macro to implement `LocalDecode` trait
+pub fn derive_local_decode(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    decode::derive_local(input)
+}
+
+#[proc_macro_derive(DecodeBorrowed)]
+/// Derive macro to implement `DecodeBorrowed` trait (supports types with lifetime parameters)
+pub fn derive_decode_borrowed(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    decode::derive_borrowed(input)
+}
+
+#[proc_macro_derive(MaxSize)]
+/// Derive macro to implement `MaxSize` trait
+pub fn derive_max_size(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    max_size::derive(input)
+}
@@ -0,0 +1,37 @@
+mod max_size_expr;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, parse_quote, DeriveInput};
+
+use self::max_size_expr::MaxSizeExpr;
+use crate::{context::Context, util::add_trait_bounds};
+
+pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    // Parse the input tokens into a syntax tree
+    let mut input = parse_macro_input!(input as DeriveInput);
+
+    // Used in the quasi-quotation below as `#name`.
+    let name = input.ident;
+
+    // Add a bound `T: MaxSize` to every type parameter T.
+    let generics = add_trait_bounds(input.generics, parse_quote!(nimble::MaxSize));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    // Create context for generating expressions
+    let context = Context::new(&name, &mut input.data);
+
+    // Generate a constant expression for calculating maximum size of encoded byte array.
+    let max_size: TokenStream = context.max_size_expr();
+
+    // Build the output, possibly using quasi-quotation
+    let expanded = quote! {
+        // The generated impl.
+        impl #impl_generics nimble::MaxSize for #name #ty_generics #where_clause {
+            const MAX_SIZE: usize = #max_size;
+        }
+    };
+
+    // Hand the output tokens back to the compiler
+    proc_macro::TokenStream::from(expanded)
+}
@@ -0,0 +1,88 @@
+use core::convert::TryFrom;
+
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::{punctuated::Iter, spanned::Spanned, Field};
+
+use crate::{
+    context::{Context, ExprType},
+    util::{FieldExt, FieldsExt},
+};
+
+pub trait MaxSizeExpr {
+    /// Returns constant expression that goes in `MaxSize::MAX_SIZE`
+    fn max_size_expr(&self) -> TokenStream;
+}
+
+impl<'a> MaxSizeExpr for Context<'a> {
+    fn max_size_expr(&self) -> TokenStream {
+        match &self.expr_type {
+            ExprType::Struct { ref fields, .. } => max_size_calculation_expr(fields.clone(), None),
+            ExprType::Enum { ref variants } => {
+                // Maximum size of an enum is the maximum of sizes of all of its variants (including variant index)
+                variants
+                    .clone()
+                    .enumerate()
+                    .fold(quote!(0), |max_size, (i, variant)| {
+                        let index = u128::try_from(i).expect("Failed to convert usize to u128. Log an issue on nimble's GitHub repository with backtrace.");
+                        let base_size_expr = quote! {
+                            nimble::__private::varint_len(#index)
+                        };
+
+                        let variant_size = max_size_calculation_expr(
+                            variant.fields.iter_fields(),
+                            Some(base_size_expr),
+                        );
+
+                        quote! {
+                            nimble::__private::max(#max_size, #variant_size)
+                        }
+                    })
+            }
+        }
+    }
+}
+
+/// Returns constant expression to calculate maximum size of all the fields
+///
+/// # Arguments
+///
+/// - `fields`: An iterator over all the fields
+/// - `base_size`: Base size expression, if any (this expression is added to size calculation expression)
+///
+/// # Example
+///
+/// For below struct:
+///
+/// ```rust,ignore
+/// struct MyStruct {
+///     a: u8,
+///     b: u16,
+/// }
+/// ```
+///
+/// This function will return:
+///
+/// ```ignore
+/// 0 + <u8 as nimble::MaxSize>::MAX_SIZE + <u16 as nimble::MaxSize>::MAX_SIZE
+/// ```
+///
+/// assuming `base_size = None`.
+fn max_size_calculation_expr(
+    fields: Iter<'_, Field>,
+    base_size: Option<TokenStream>,
+) -> TokenStream {
+    let recurse = fields.map(|f| {
+        let field_type = f.get_type();
+
+        quote_spanned! {f.span()=>
+            <#field_type as nimble::MaxSize>::MAX_SIZE
+        }
+    });
+
+    let base_size = base_size.unwrap_or_else(|| quote! {0});
+
+    quote! {
+        #base_size #(+ #recurse)*
+    }
+}
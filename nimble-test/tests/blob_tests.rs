@@ -0,0 +1,99 @@
+use futures_executor as executor;
+use futures_util::io::{AsyncReadExt, Cursor};
+
+use nimble::{encode_to, io::FuturesIo, Blob, Config, Decode, Encode, Error};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Upload {
+    name: String,
+    data: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct UploadHeader {
+    name: String,
+}
+
+fn payload() -> Vec<u8> {
+    (0..200_000u32).map(|i| i as u8).collect()
+}
+
+#[test]
+fn blob_encode_test() {
+    executor::block_on(async {
+        let config = Config::default();
+
+        let mut bytes = Vec::new();
+        encode_to(
+            &UploadHeader {
+                name: "file".to_owned(),
+            },
+            &mut bytes,
+        )
+        .await
+        .unwrap();
+
+        let mut blob = Blob::new(
+            payload().len() as u64,
+            FuturesIo::new(Cursor::new(payload())),
+        );
+        let written = blob.encode_to(&config, &mut bytes).await.unwrap();
+
+        assert_eq!(payload().len() + 3, written);
+
+        let upload: Upload = nimble::decode(&bytes).await.unwrap();
+
+        assert_eq!(
+            Upload {
+                name: "file".to_owned(),
+                data: payload(),
+            },
+            upload
+        );
+
+        let mut blob = Blob::new(10, &[1u8, 2, 3][..]);
+        assert!(matches!(
+            blob.encode_to(&config, Vec::new()).await,
+            Err(Error::UnexpectedEof)
+        ));
+    });
+}
+
+#[test]
+fn blob_decode_test() {
+    executor::block_on(async {
+        let config = Config::default();
+
+        let mut bytes = nimble::encode(&Upload {
+            name: "file".to_owned(),
+            data: payload(),
+        })
+        .await;
+        bytes.push(42);
+
+        let mut reader = &bytes[..];
+
+        let header: UploadHeader = nimble::decode_from(&mut reader).await.unwrap();
+        assert_eq!("file", header.name);
+
+        let blob = Blob::decode_from(&config, &mut reader).await.unwrap();
+        assert_eq!(payload().len() as u64, blob.len());
+
+        let mut blob_reader = blob.into_inner();
+        let mut data = Vec::new();
+        blob_reader.read_to_end(&mut data).await.unwrap();
+
+        assert_eq!(payload(), data);
+        assert_eq!(0, blob_reader.remaining());
+        assert_eq!(&[42], blob_reader.into_inner());
+
+        let blob = Blob::decode_from(&config, &bytes[5..bytes.len() - 100])
+            .await
+            .unwrap();
+
+        let mut data = Vec::new();
+        let err = blob.into_inner().read_to_end(&mut data).await.unwrap_err();
+
+        assert_eq!(std::io::ErrorKind::UnexpectedEof, err.kind());
+    });
+}
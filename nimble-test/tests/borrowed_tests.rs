@@ -0,0 +1,76 @@
+use std::borrow::Cow;
+
+use futures_executor as executor;
+
+use nimble::{decode_borrowed, encode, DecodeBorrowed, Encode};
+
+#[derive(Debug, PartialEq, Encode, DecodeBorrowed)]
+struct Borrowed<'a> {
+    id: u32,
+    name: &'a str,
+    payload: &'a [u8],
+    tags: Vec<Cow<'a, str>>,
+    parent: Option<Box<Borrowed<'a>>>,
+}
+
+#[derive(Debug, PartialEq, Encode, DecodeBorrowed)]
+enum Event<'a, T> {
+    Empty,
+    Named { name: &'a str, value: T },
+    Pair(&'a str, &'a [u8]),
+}
+
+#[test]
+fn borrowed_struct_test() {
+    let original = Borrowed {
+        id: 1,
+        name: "hello",
+        payload: &[1, 2, 3],
+        tags: vec![Cow::Borrowed("a"), Cow::Owned("b".to_owned())],
+        parent: Some(Box::new(Borrowed {
+            id: 0,
+            name: "parent",
+            payload: &[],
+            tags: vec![],
+            parent: None,
+        })),
+    };
+
+    let encoded = executor::block_on(encode(&original));
+    assert_eq!(original.size(), encoded.len());
+
+    let decoded: Borrowed<'_> = decode_borrowed(&encoded).unwrap();
+    assert_eq!(original, decoded);
+
+    // Decoded values point into encoded bytes
+    let range = encoded.as_ptr_range();
+    assert!(range.contains(&decoded.name.as_ptr()));
+    assert!(range.contains(&decoded.payload.as_ptr()));
+    assert!(matches!(decoded.tags[1], Cow::Borrowed(_)));
+}
+
+#[test]
+fn borrowed_enum_test() {
+    for original in [
+        Event::Empty,
+        Event::Named {
+            name: "count",
+            value: 10u64,
+        },
+        Event::Pair("key", b"value"),
+    ] {
+        let encoded = executor::block_on(encode(&original));
+        let decoded: Event<'_, u64> = decode_borrowed(&encoded).unwrap();
+        assert_eq!(original, decoded);
+    }
+}
+
+#[test]
+fn borrowed_invalid_utf8_test() {
+    let encoded = executor::block_on(encode(&[0xffu8, 0xfe][..]));
+    let result: nimble::Result<&str> = decode_borrowed(&encoded);
+    assert!(matches!(result, Err(nimble::Error::InvalidUtf8Str(_))));
+
+    let result: nimble::Result<&[u8]> = decode_borrowed(&encoded[..2]);
+    assert!(matches!(result, Err(nimble::Error::UnexpectedEof)));
+}
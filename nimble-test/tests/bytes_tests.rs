@@ -0,0 +1,105 @@
+use bytes::{Bytes, BytesMut};
+use futures_executor as executor;
+
+use nimble::{decode, decode_bytes, encode, encode_to_buf, Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Packet {
+    id: u32,
+    headers: Vec<(String, Bytes)>,
+    body: Bytes,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+enum Message {
+    Ping,
+    Data(Bytes),
+    Packet(Packet),
+}
+
+fn contains(outer: &Bytes, inner: &Bytes) -> bool {
+    let range = outer.as_ptr_range();
+    inner.is_empty() || (range.contains(&inner.as_ptr()) && inner.as_ptr_range().end <= range.end)
+}
+
+#[test]
+fn zero_copy_struct_test() {
+    let original = Packet {
+        id: 7,
+        headers: vec![("type".to_owned(), Bytes::from_static(b"raw"))],
+        body: Bytes::from(vec![1u8; 1024]),
+    };
+
+    let encoded = Bytes::from(executor::block_on(encode(&original)));
+    let decoded: Packet = decode_bytes(encoded.clone()).unwrap();
+
+    assert_eq!(original, decoded);
+    assert!(contains(&encoded, &decoded.body));
+    assert!(contains(&encoded, &decoded.headers[0].1));
+}
+
+#[test]
+fn zero_copy_enum_test() {
+    let original = Message::Packet(Packet {
+        id: 1,
+        headers: vec![],
+        body: Bytes::from_static(b"hello"),
+    });
+
+    let encoded = Bytes::from(executor::block_on(encode(&original)));
+    let decoded: Message = decode_bytes(encoded.clone()).unwrap();
+
+    assert_eq!(original, decoded);
+    match decoded {
+        Message::Packet(packet) => assert!(contains(&encoded, &packet.body)),
+        _ => unreachable!(),
+    }
+
+    let encoded = Bytes::from(executor::block_on(encode(&Message::Data(Bytes::new()))));
+    assert_eq!(Message::Data(Bytes::new()), decode_bytes(encoded).unwrap());
+}
+
+#[test]
+fn bytes_compatibility_test() {
+    let bytes = Bytes::from_static(b"hello");
+    let bytes_mut = BytesMut::from(&b"hello"[..]);
+
+    let encoded = executor::block_on(encode(&bytes));
+    assert_eq!(encoded, executor::block_on(encode(&bytes_mut)));
+    assert_eq!(encoded, executor::block_on(encode(&b"hello".to_vec())));
+
+    let decoded: BytesMut = executor::block_on(decode(&encoded)).unwrap();
+    assert_eq!(bytes_mut, decoded);
+
+    let decoded: Bytes = executor::block_on(decode(&encoded)).unwrap();
+    assert_eq!(bytes, decoded);
+}
+
+#[test]
+fn encode_to_buf_test() {
+    let original = Packet {
+        id: 7,
+        headers: vec![("type".to_owned(), Bytes::from_static(b"raw"))],
+        body: Bytes::from_static(b"body"),
+    };
+
+    let mut buf = BytesMut::new();
+    let written = encode_to_buf(&original, &mut buf).unwrap();
+
+    assert_eq!(original.size(), written);
+    assert_eq!(executor::block_on(encode(&original)), buf.as_ref());
+
+    let mut slice = [0u8; 4];
+    assert!(matches!(
+        encode_to_buf(&original, &mut &mut slice[..]),
+        Err(nimble::Error::WriteZero)
+    ));
+
+    let decoded: Packet = decode_bytes(buf.freeze()).unwrap();
+    assert_eq!(original, decoded);
+
+    assert!(matches!(
+        decode_bytes::<Packet>(Bytes::from_static(&[7, 0, 0])),
+        Err(nimble::Error::UnexpectedEof)
+    ));
+}
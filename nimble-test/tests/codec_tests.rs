@@ -0,0 +1,111 @@
+use bytes::{BufMut, BytesMut};
+use futures_executor as executor;
+use futures_util::{SinkExt, StreamExt};
+use tokio_util::codec::{Decoder, Encoder, FramedRead, FramedWrite, LengthDelimitedCodec};
+
+use nimble::{codec::NimbleCodec, Config, Decode, Encode, Endianness};
+
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+struct Message {
+    id: u64,
+    body: String,
+}
+
+fn messages() -> Vec<Message> {
+    (0..3)
+        .map(|id| Message {
+            id,
+            body: "hello".repeat(id as usize),
+        })
+        .collect()
+}
+
+#[test]
+fn framed_test() {
+    executor::block_on(async {
+        let mut writer = FramedWrite::new(Vec::new(), NimbleCodec::<Message>::new());
+
+        for message in messages() {
+            writer.send(message).await.unwrap();
+        }
+
+        let bytes = writer.into_inner();
+
+        let reader = FramedRead::new(&bytes[..], NimbleCodec::<Message>::new());
+        let decoded: Vec<Message> = reader.map(Result::unwrap).collect().await;
+        assert_eq!(messages(), decoded);
+
+        // Frames are compatible with default `LengthDelimitedCodec`
+        let reader = FramedRead::new(&bytes[..], LengthDelimitedCodec::new());
+        let frames: Vec<BytesMut> = reader.map(Result::unwrap).collect().await;
+        assert_eq!(3, frames.len());
+        assert_eq!(
+            messages()[2],
+            nimble::decode_bytes(frames[2].clone().freeze()).unwrap()
+        );
+    });
+}
+
+#[test]
+fn partial_frame_test() {
+    let config = Config::default().with_endianness(Endianness::BigEndian);
+    let mut codec = NimbleCodec::<Message>::with_config(config);
+
+    let mut encoded = BytesMut::new();
+    codec.encode(messages()[1].clone(), &mut encoded).unwrap();
+
+    let mut src = BytesMut::new();
+
+    for byte in &encoded[..encoded.len() - 1] {
+        src.put_u8(*byte);
+        assert!(codec.decode(&mut src).unwrap().is_none());
+    }
+
+    src.put_u8(encoded[encoded.len() - 1]);
+    assert_eq!(Some(messages()[1].clone()), codec.decode(&mut src).unwrap());
+    assert!(src.is_empty());
+}
+
+#[test]
+fn max_frame_size_test() {
+    assert_eq!(
+        nimble::DEFAULT_MAX_VALUE_SIZE,
+        NimbleCodec::<Message>::new().max_frame_size()
+    );
+
+    let mut codec = NimbleCodec::<Message>::new().with_max_frame_size(16);
+    assert_eq!(16, codec.max_frame_size());
+
+    let mut dst = BytesMut::new();
+    codec.encode(messages()[1].clone(), &mut dst).unwrap();
+
+    assert!(matches!(
+        codec.encode(messages()[2].clone(), &mut dst),
+        Err(nimble::Error::FrameTooLarge { size: 19, max: 16 })
+    ));
+
+    let mut src = BytesMut::new();
+    src.put_u32(17);
+    assert!(matches!(
+        codec.decode(&mut src),
+        Err(nimble::Error::FrameTooLarge { size: 17, max: 16 })
+    ));
+}
+
+#[test]
+fn trailing_bytes_test() {
+    let mut codec = NimbleCodec::<Message>::new();
+
+    let encoded = executor::block_on(nimble::encode(&messages()[1]));
+
+    let mut src = BytesMut::new();
+    src.put_u32(encoded.len() as u32 + 2);
+    src.extend_from_slice(&encoded);
+    src.extend_from_slice(&[0, 0]);
+
+    assert!(matches!(
+        codec.decode(&mut src),
+        Err(nimble::Error::TrailingBytes(2))
+    ));
+    assert!(src.is_empty());
+}
@@ -1,8 +1,8 @@
 use futures_executor as executor;
 
-use nimble::{decode, encode, Decode, Encode};
+use nimble::{decode, encode, encode_into_slice, Decode, Encode, MaxSize};
 
-#[derive(Debug, PartialEq, Encode, Decode)]
+#[derive(Debug, PartialEq, Encode, Decode, MaxSize)]
 enum MyEnum {
     Unit,
     Unnamed(u8, u16),
@@ -50,3 +50,39 @@ fn enum_named_variant_test() {
         assert_eq!(original, decoded);
     });
 }
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+enum List {
+    Nil,
+    Cons(u8, Box<List>),
+}
+
+#[test]
+fn recursive_enum_test() {
+    executor::block_on(async {
+        let original = List::Cons(10, Box::new(List::Cons(20, Box::new(List::Nil))));
+
+        assert_eq!(5, original.size());
+        let encoded = encode(&original).await;
+        assert_eq!(encoded.len(), original.size());
+        let decoded: List = decode(&encoded).await.unwrap();
+
+        assert_eq!(original, decoded);
+    });
+}
+
+#[test]
+fn enum_max_size_test() {
+    assert_eq!(4, MyEnum::MAX_SIZE);
+
+    let mut buf = [0u8; MyEnum::MAX_SIZE];
+
+    for value in [
+        MyEnum::Unit,
+        MyEnum::Unnamed(10, 20),
+        MyEnum::Named { a: 10, b: 20 },
+    ] {
+        assert!(value.size() <= MyEnum::MAX_SIZE);
+        assert_eq!(value.size(), encode_into_slice(&value, &mut buf).unwrap());
+    }
+}
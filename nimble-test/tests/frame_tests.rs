@@ -0,0 +1,135 @@
+use futures_executor as executor;
+
+use nimble::{
+    frame::{self, Frame},
+    io::Write,
+    Config, Decode, Encode, Error, Result,
+};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Message {
+    id: u64,
+    body: String,
+}
+
+fn message() -> Message {
+    Message {
+        id: 7,
+        body: "hello".to_owned(),
+    }
+}
+
+#[test]
+fn frame_test() {
+    executor::block_on(async {
+        let config = Config::default();
+
+        let mut bytes = Vec::new();
+        let written = frame::write_frame(&config, 3, &message(), &mut bytes)
+            .await
+            .unwrap();
+
+        assert_eq!(bytes.len(), written);
+        assert_eq!(&bytes[..4], &frame::MAGIC);
+        assert_eq!(bytes[4], frame::VERSION);
+        assert_eq!(bytes[5], 3);
+        assert_eq!(&bytes[6..10], &14u32.to_be_bytes());
+
+        let frame: Frame<Message> =
+            frame::read_frame(&config, frame::DEFAULT_MAX_PAYLOAD_SIZE, &bytes[..])
+                .await
+                .unwrap();
+
+        assert_eq!(
+            Frame {
+                flags: 3,
+                value: message()
+            },
+            frame
+        );
+    });
+}
+
+/// Type whose `size()` does not match its encoded size
+struct Misreported(u32);
+
+impl Encode for Misreported {
+    fn size(&self) -> usize {
+        0
+    }
+
+    async fn encode_to<W>(&self, config: &Config, writer: W) -> Result<usize>
+    where
+        W: Write + Unpin + Send,
+    {
+        self.0.encode_to(config, writer).await
+    }
+}
+
+#[test]
+fn frame_size_test() {
+    executor::block_on(async {
+        let config = Config::default();
+
+        let mut bytes = Vec::new();
+        frame::write_frame(&config, 0, &Misreported(42), &mut bytes)
+            .await
+            .unwrap();
+        assert_eq!(&bytes[6..10], &4u32.to_be_bytes());
+
+        let frame: Frame<u32> =
+            frame::read_frame(&config, frame::DEFAULT_MAX_PAYLOAD_SIZE, &bytes[..])
+                .await
+                .unwrap();
+        assert_eq!(42, frame.value);
+    });
+}
+
+#[test]
+fn frame_error_test() {
+    executor::block_on(async {
+        let config = Config::default();
+
+        let mut bytes = Vec::new();
+        frame::write_frame(&config, 0, &message(), &mut bytes)
+            .await
+            .unwrap();
+
+        let read = |bytes: Vec<u8>, max: usize| {
+            let config = config.clone();
+            async move { frame::read_frame::<Message, _>(&config, max, &bytes[..]).await }
+        };
+
+        let mut invalid = bytes.clone();
+        invalid[0] = b'X';
+        assert!(matches!(
+            read(invalid, 1024).await,
+            Err(Error::InvalidFrameMagic(magic)) if &magic == b"XMBL"
+        ));
+
+        let mut invalid = bytes.clone();
+        invalid[4] = frame::VERSION + 1;
+        assert!(matches!(
+            read(invalid, 1024).await,
+            Err(Error::UnsupportedFrameVersion(version)) if version == frame::VERSION + 1
+        ));
+
+        assert!(matches!(
+            read(bytes.clone(), 8).await,
+            Err(Error::FrameTooLarge { size: 14, max: 8 })
+        ));
+
+        assert!(matches!(
+            read(bytes[..bytes.len() - 1].to_vec(), 1024).await,
+            Err(Error::UnexpectedEof)
+        ));
+
+        let mut invalid = bytes.clone();
+        invalid.push(0);
+        invalid[9] += 1;
+        assert!(matches!(
+            read(invalid, 1024).await,
+            Err(Error::TrailingBytes(1))
+        ));
+    });
+}
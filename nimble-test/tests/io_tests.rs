@@ -0,0 +1,236 @@
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_executor as executor;
+use futures_util::io::Cursor;
+
+use nimble::{
+    config, decode_from, encode_to,
+    io::{EmbeddedAsyncIo, EmbeddedIo, FuturesIo, TokioIo, Write},
+    Config, Decode, Encode,
+};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct MyStruct {
+    a: u8,
+    b: Vec<u16>,
+    c: String,
+}
+
+fn my_struct() -> MyStruct {
+    MyStruct {
+        a: 10,
+        b: vec![20, 30],
+        c: "hello".to_owned(),
+    }
+}
+
+#[test]
+fn in_memory_sync_test() {
+    let original = my_struct();
+
+    let mut bytes = Vec::new();
+    let written = original.encode_to_vec(config(), &mut bytes);
+    assert_eq!(original.size(), written);
+    assert_eq!(executor::block_on(nimble::encode(&original)), bytes);
+
+    let decoded = MyStruct::decode_from_slice(config(), &mut &bytes[..]).unwrap();
+    assert_eq!(original, decoded);
+}
+
+#[test]
+fn futures_io_test() {
+    executor::block_on(async {
+        let original = my_struct();
+
+        let mut writer = FuturesIo::new(Cursor::new(Vec::new()));
+        let written = encode_to(&original, &mut writer).await.unwrap();
+        assert_eq!(original.size(), written);
+
+        let bytes = writer.into_inner().into_inner();
+        assert_eq!(original.size(), bytes.len());
+
+        let decoded: MyStruct = decode_from(FuturesIo::new(Cursor::new(bytes)))
+            .await
+            .unwrap();
+        assert_eq!(original, decoded);
+    });
+}
+
+#[test]
+fn tokio_io_test() {
+    executor::block_on(async {
+        let original = my_struct();
+
+        let mut writer = TokioIo::new(Vec::new());
+        let written = encode_to(&original, &mut writer).await.unwrap();
+        assert_eq!(original.size(), written);
+
+        let bytes = writer.into_inner();
+        assert_eq!(original.size(), bytes.len());
+
+        let decoded: MyStruct = decode_from(TokioIo::new(&bytes[..])).await.unwrap();
+        assert_eq!(original, decoded);
+    });
+}
+
+#[test]
+fn embedded_io_test() {
+    executor::block_on(async {
+        let original = my_struct();
+
+        let mut buf = [0u8; 64];
+        let mut writer = EmbeddedIo::new(&mut buf[..]);
+        let written = encode_to(&original, &mut writer).await.unwrap();
+        assert_eq!(original.size(), written);
+
+        let decoded: MyStruct = decode_from(EmbeddedIo::new(&buf[..written])).await.unwrap();
+        assert_eq!(original, decoded);
+
+        let mut small = [0u8; 4];
+        let result = encode_to(&original, EmbeddedIo::new(&mut small[..])).await;
+        assert!(matches!(result, Err(nimble::Error::EmbeddedIoError(_))));
+    });
+}
+
+#[test]
+fn embedded_async_io_test() {
+    executor::block_on(async {
+        let original = my_struct();
+
+        let mut buf = [0u8; 64];
+        let mut writer = EmbeddedAsyncIo::new(&mut buf[..]);
+        let first = writer.encode(config(), &original).await.unwrap();
+        let second = writer.encode(config(), &7u32).await.unwrap();
+        assert_eq!(original.size() + 7u32.size(), first + second);
+
+        let mut reader = EmbeddedAsyncIo::new(&buf[..first + second]);
+        let decoded: MyStruct = reader.decode(config()).await.unwrap();
+        assert_eq!(original, decoded);
+        assert_eq!(second, reader.get_ref().len());
+        assert_eq!(7, reader.decode::<u32>(config()).await.unwrap());
+
+        let result = reader.decode::<u32>(config()).await;
+        assert!(matches!(result, Err(nimble::Error::UnexpectedEof)));
+
+        // Bytes after an invalid value are not read
+        let mut reader = EmbeddedAsyncIo::new(&[2, 7, 0, 0, 0][..]);
+        let result = reader.decode::<Option<u8>>(config()).await;
+        assert!(matches!(result, Err(nimble::Error::InvalidEnumVariant(_))));
+        assert_eq!(7, reader.decode::<u32>(config()).await.unwrap());
+
+        let mut small = [0u8; 4];
+        let result = EmbeddedAsyncIo::new(&mut small[..])
+            .encode(config(), &original)
+            .await;
+        assert!(matches!(result, Err(nimble::Error::EmbeddedIoError(_))));
+    });
+}
+
+#[test]
+fn unexpected_eof_test() {
+    executor::block_on(async {
+        let original = my_struct();
+        let bytes = nimble::encode(&original).await;
+
+        let result: nimble::Result<MyStruct> =
+            decode_from(TokioIo::new(&bytes[..bytes.len() - 1])).await;
+        assert!(matches!(result, Err(nimble::Error::UnexpectedEof)));
+    });
+}
+
+#[test]
+fn blocking_io_test() {
+    let original = my_struct();
+
+    let mut writer = std::io::Cursor::new(Vec::new());
+    let written = nimble::encode_to_writer(&original, &mut writer).unwrap();
+    assert_eq!(original.size(), written);
+
+    let bytes = writer.into_inner();
+    assert_eq!(executor::block_on(nimble::encode(&original)), bytes);
+
+    let decoded: MyStruct = nimble::decode_from_reader(&bytes[..]).unwrap();
+    assert_eq!(original, decoded);
+
+    let result: nimble::Result<MyStruct> = nimble::decode_from_reader(&bytes[..bytes.len() - 1]);
+    assert!(matches!(result, Err(nimble::Error::UnexpectedEof)));
+}
+
+/// Writer which records number of `poll_write` and `poll_flush` calls
+#[derive(Default)]
+struct CountingWriter {
+    bytes: Vec<u8>,
+    writes: usize,
+    flushes: usize,
+}
+
+impl Write for CountingWriter {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<nimble::Result<usize>> {
+        self.writes += 1;
+        self.bytes.extend_from_slice(buf);
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<nimble::Result<()>> {
+        self.flushes += 1;
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<nimble::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[test]
+fn buffered_encode_to_test() {
+    executor::block_on(async {
+        let original = my_struct();
+
+        let mut writer = CountingWriter::default();
+        let written = encode_to(&original, &mut writer).await.unwrap();
+        assert_eq!(original.size(), written);
+        assert_eq!(1, writer.writes);
+        assert_eq!(0, writer.flushes);
+        assert_eq!(nimble::encode(&original).await, writer.bytes);
+
+        let config = Config::default().with_flush(true);
+
+        let mut writer = CountingWriter::default();
+        config.encode_to(&original, &mut writer).await.unwrap();
+        assert_eq!(1, writer.writes);
+        assert_eq!(1, writer.flushes);
+    });
+}
+
+#[test]
+fn bulk_bytes_test() {
+    executor::block_on(async {
+        let original: Vec<u8> = (0..=255).cycle().take(10_000).collect();
+
+        let mut writer = CountingWriter::default();
+        let written = original.encode_to(config(), &mut writer).await.unwrap();
+        assert_eq!(original.size(), written);
+        // One write for length prefix and one for all the bytes
+        assert_eq!(2, writer.writes);
+
+        let mut writer = CountingWriter::default();
+        "hello".encode_to(config(), &mut writer).await.unwrap();
+        assert_eq!(2, writer.writes);
+
+        let decoded: Vec<u8> = decode_from(FuturesIo::new(Cursor::new(&writer.bytes)))
+            .await
+            .unwrap();
+        assert_eq!(b"hello".to_vec(), decoded);
+
+        let result: nimble::Result<Vec<u8>> =
+            decode_from(FuturesIo::new(Cursor::new(&writer.bytes[..4]))).await;
+        assert!(matches!(result, Err(nimble::Error::UnexpectedEof)));
+    });
+}
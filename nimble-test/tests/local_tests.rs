@@ -0,0 +1,108 @@
+use std::{cell::RefCell, rc::Rc};
+
+use futures_executor as executor;
+
+use nimble::{Config, Encode, Endianness, LocalDecode, LocalEncode};
+
+#[derive(Debug, PartialEq, LocalEncode, LocalDecode)]
+struct Node {
+    value: u32,
+    children: Vec<Rc<RefCell<Node>>>,
+}
+
+#[derive(Debug, PartialEq, Encode, LocalEncode)]
+enum Shape {
+    Point,
+    Circle { radius: u32 },
+    Polygon(Vec<(i32, i32)>, String),
+}
+
+#[test]
+fn rc_ref_cell_test() {
+    let leaf = Rc::new(RefCell::new(Node {
+        value: 2,
+        children: vec![],
+    }));
+    let original = Node {
+        value: 1,
+        children: vec![leaf.clone(), leaf.clone()],
+    };
+
+    leaf.borrow_mut().value = 3;
+
+    let mut encoded = Vec::new();
+    let written = original.encode_to_vec_local(nimble::config(), &mut encoded);
+    assert_eq!(encoded.len(), written);
+
+    let decoded = Node::decode_from_slice_local(nimble::config(), &mut &encoded[..]).unwrap();
+
+    assert_eq!(original, decoded);
+    assert_eq!(3, decoded.children[1].borrow().value);
+    assert!(!Rc::ptr_eq(&decoded.children[0], &decoded.children[1]));
+}
+
+#[test]
+fn local_encoding_test() {
+    let shapes = vec![
+        Shape::Point,
+        Shape::Circle { radius: 7 },
+        Shape::Polygon(vec![(0, 0), (1, -1)], "triangle".to_owned()),
+    ];
+
+    let mut encoded = Vec::new();
+    shapes.encode_to_vec_local(nimble::config(), &mut encoded);
+
+    assert_eq!(
+        shapes.encode_to_vec(nimble::config(), &mut Vec::new()),
+        encoded.len()
+    );
+    assert_eq!(executor::block_on(nimble::encode(&shapes)), encoded);
+}
+
+#[test]
+fn local_future_test() {
+    let original = Rc::new(RefCell::new(vec![Rc::new("hello".to_owned())]));
+
+    executor::LocalPool::new().run_until(async {
+        let mut encoded = Vec::new();
+        let written = original
+            .encode_to_local(nimble::config(), &mut encoded)
+            .await
+            .unwrap();
+        assert_eq!(encoded.len(), written);
+
+        let decoded =
+            <Rc<RefCell<Vec<Rc<String>>>>>::decode_from_local(nimble::config(), &encoded[..])
+                .await
+                .unwrap();
+        assert_eq!(original, decoded);
+    });
+}
+
+#[test]
+fn local_primitive_seq_test() {
+    let floats: Vec<f64> = (0..1000).map(|i| i as f64 / 3.0).collect();
+    let bytes: Vec<u8> = (0..100_000u32).map(|i| i as u8).collect();
+
+    for endianness in [Endianness::LittleEndian, Endianness::BigEndian] {
+        let config = Config::default().with_endianness(endianness);
+        let value = (floats.clone(), bytes.clone(), [1u16, 2, 3]);
+
+        let expected = executor::block_on(config.encode(&value));
+
+        let mut encoded = Vec::new();
+        value.encode_to_vec_local(&config, &mut encoded);
+        assert_eq!(expected, encoded);
+
+        let mut encoded = Vec::new();
+        executor::block_on(value.encode_to_local(&config, &mut encoded)).unwrap();
+        assert_eq!(expected, encoded);
+
+        let decoded = executor::block_on(<(Vec<f64>, Vec<u8>, [u16; 3])>::decode_from_local(
+            &config,
+            &encoded[..],
+        ))
+        .unwrap();
+        assert_eq!(value, decoded);
+    }
+}
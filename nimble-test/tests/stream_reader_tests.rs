@@ -0,0 +1,86 @@
+use bytes::Bytes;
+use futures_executor as executor;
+use futures_util::{stream, StreamExt};
+
+use nimble::{
+    decode_from, encode, io::StreamReader, stream::decode_stream, Config, Decode, Encode, Error,
+};
+
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+struct Record {
+    id: u32,
+    name: String,
+}
+
+fn records() -> Vec<Record> {
+    (0..10)
+        .map(|id| Record {
+            id,
+            name: "record".repeat(id as usize),
+        })
+        .collect()
+}
+
+/// Splits bytes into chunks of given size (including empty chunks) to straddle values across chunk boundaries
+fn chunks(bytes: &[u8], size: usize) -> Vec<Result<Bytes, Error>> {
+    bytes
+        .chunks(size)
+        .flat_map(|chunk| vec![Ok(Bytes::new()), Ok(Bytes::copy_from_slice(chunk))])
+        .collect()
+}
+
+#[test]
+fn stream_reader_test() {
+    executor::block_on(async {
+        let bytes = encode(&records()).await;
+
+        for size in [1, 3, 7, 64] {
+            let reader = StreamReader::new(stream::iter(chunks(&bytes, size)));
+            let decoded: Vec<Record> = decode_from(reader).await.unwrap();
+
+            assert_eq!(records(), decoded);
+        }
+    });
+}
+
+#[test]
+fn stream_reader_decode_stream_test() {
+    executor::block_on(async {
+        let mut bytes = Vec::new();
+
+        for record in records() {
+            bytes.extend(encode(&record).await);
+        }
+
+        let reader = StreamReader::new(stream::iter(chunks(&bytes, 5)));
+        let decoded: Vec<Record> = decode_stream(Config::default(), reader)
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        assert_eq!(records(), decoded);
+    });
+}
+
+#[test]
+fn stream_reader_error_test() {
+    executor::block_on(async {
+        let bytes = encode(&records()).await;
+
+        let reader = StreamReader::new(stream::iter(chunks(&bytes[..bytes.len() / 2], 4)));
+
+        assert!(matches!(
+            decode_from::<Vec<Record>, _>(reader).await,
+            Err(Error::UnexpectedEof)
+        ));
+
+        let mut chunks = chunks(&bytes[..bytes.len() / 2], 4);
+        chunks.push(Err(std::io::Error::other("connection reset").into()));
+        let reader = StreamReader::new(stream::iter(chunks));
+
+        assert!(matches!(
+            decode_from::<Vec<Record>, _>(reader).await,
+            Err(Error::IoError(_))
+        ));
+    });
+}
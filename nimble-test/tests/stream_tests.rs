@@ -0,0 +1,172 @@
+use futures_executor as executor;
+use futures_util::{io::Cursor, pin_mut, stream, SinkExt, StreamExt};
+
+use nimble::{
+    encode,
+    io::FuturesIo,
+    stream::{decode_chunked_stream, decode_seq_stream, decode_stream},
+    ChunkedEncoder, Config, Decode, Encode, Endianness, SeqEncoder, Sink,
+};
+
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+enum Event {
+    Created { id: u32, name: String },
+    Deleted(u32),
+}
+
+fn events() -> Vec<Event> {
+    vec![
+        Event::Created {
+            id: 1,
+            name: "first".to_owned(),
+        },
+        Event::Deleted(1),
+        Event::Created {
+            id: 2,
+            name: "second".to_owned(),
+        },
+    ]
+}
+
+async fn encode_events(events: &[Event]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+
+    for event in events {
+        bytes.extend(encode(event).await);
+    }
+
+    bytes
+}
+
+#[test]
+fn decode_stream_test() {
+    executor::block_on(async {
+        let bytes = encode_events(&events()).await;
+
+        let stream =
+            decode_stream::<Event, _>(Config::default(), FuturesIo::new(Cursor::new(bytes)));
+        let decoded: Vec<Event> = stream.map(Result::unwrap).collect().await;
+
+        assert_eq!(events(), decoded);
+
+        let stream = decode_stream::<Event, _>(Config::default(), &[][..]);
+        assert_eq!(0, stream.count().await);
+    });
+}
+
+#[test]
+fn decode_stream_partial_test() {
+    executor::block_on(async {
+        let bytes = encode_events(&events()).await;
+
+        let stream = decode_stream::<Event, _>(Config::default(), &bytes[..bytes.len() - 1]);
+        pin_mut!(stream);
+
+        assert_eq!(events()[0], stream.next().await.unwrap().unwrap());
+        assert_eq!(events()[1], stream.next().await.unwrap().unwrap());
+        assert!(matches!(
+            stream.next().await,
+            Some(Err(nimble::Error::UnexpectedEof))
+        ));
+        assert!(stream.next().await.is_none());
+    });
+}
+
+#[test]
+fn decode_seq_stream_test() {
+    executor::block_on(async {
+        let mut bytes = encode(&events()).await;
+        bytes.extend(encode(&Event::Deleted(2)).await);
+
+        let mut reader = &bytes[..];
+
+        let stream = decode_seq_stream::<Event, _>(Config::default(), &mut reader);
+        let decoded: Vec<Event> = stream.map(Result::unwrap).collect().await;
+
+        assert_eq!(events(), decoded);
+        assert_eq!(encode(&Event::Deleted(2)).await, reader);
+
+        let bytes = encode(&events()).await;
+
+        let stream = decode_seq_stream::<Event, _>(Config::default(), &bytes[..bytes.len() - 1]);
+        let decoded: Vec<_> = stream.collect().await;
+
+        assert_eq!(3, decoded.len());
+        assert!(matches!(decoded[2], Err(nimble::Error::UnexpectedEof)));
+
+        let stream = decode_seq_stream::<Event, _>(Config::default(), &[][..]);
+        pin_mut!(stream);
+
+        assert!(matches!(
+            stream.next().await,
+            Some(Err(nimble::Error::UnexpectedEof))
+        ));
+        assert!(stream.next().await.is_none());
+    });
+}
+
+#[test]
+fn seq_encoder_stream_test() {
+    executor::block_on(async {
+        let mut bytes = Vec::new();
+
+        let mut encoder = SeqEncoder::<Event, _>::new(events().len(), &mut bytes)
+            .await
+            .unwrap();
+        encoder.encode_stream(stream::iter(events())).await.unwrap();
+        encoder.finish().await.unwrap();
+
+        assert_eq!(encode(&events()).await, bytes);
+    });
+}
+
+#[test]
+fn chunked_stream_test() {
+    executor::block_on(async {
+        let mut bytes = Vec::new();
+
+        let mut encoder = ChunkedEncoder::new(&mut bytes);
+        encoder.set_max_chunk_len(2);
+        encoder.encode_stream(stream::iter(events())).await.unwrap();
+        encoder.finish().await.unwrap();
+
+        let stream = decode_chunked_stream::<Event, _>(Config::default(), &bytes[..]);
+        let decoded: Vec<Event> = stream.map(Result::unwrap).collect().await;
+
+        assert_eq!(events(), decoded);
+
+        let stream =
+            decode_chunked_stream::<Event, _>(Config::default(), &bytes[..bytes.len() - 1]);
+        let decoded: Vec<_> = stream.collect().await;
+
+        assert_eq!(4, decoded.len());
+        assert!(matches!(decoded[3], Err(nimble::Error::UnexpectedEof)));
+    });
+}
+
+#[test]
+fn sink_test() {
+    executor::block_on(async {
+        let mut sink = Sink::new(FuturesIo::new(Cursor::new(Vec::new())));
+
+        stream::iter(events())
+            .map(Ok)
+            .forward(&mut sink)
+            .await
+            .unwrap();
+
+        let bytes = sink.into_inner().into_inner().into_inner();
+        assert_eq!(encode_events(&events()).await, bytes);
+
+        let config = Config::default().with_endianness(Endianness::BigEndian);
+
+        let mut sink = Sink::with_config(config.clone(), Vec::new());
+        sink.send(Event::Deleted(1)).await.unwrap();
+        sink.feed(Event::Deleted(2)).await.unwrap();
+        sink.close().await.unwrap();
+
+        let stream = decode_stream::<Event, _>(config, &sink.get_ref()[..]);
+        let decoded: Vec<Event> = stream.map(Result::unwrap).collect().await;
+        assert_eq!(vec![Event::Deleted(1), Event::Deleted(2)], decoded);
+    });
+}
@@ -1,6 +1,6 @@
 use futures_executor as executor;
 
-use nimble::{decode, encode, Decode, Encode};
+use nimble::{decode, encode, Decode, Encode, MaxSize};
 
 #[test]
 fn unit_struct_test() {
@@ -55,3 +55,160 @@ fn named_struct_test() {
         assert_eq!(original, decoded);
     });
 }
+
+#[test]
+fn generic_struct_test() {
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    struct GenericStruct<T> {
+        a: T,
+        b: Vec<T>,
+    }
+
+    executor::block_on(async {
+        let original = GenericStruct {
+            a: 10u16,
+            b: vec![20, 30],
+        };
+
+        assert_eq!(7, original.size());
+        let encoded = encode(&original).await;
+        assert_eq!(encoded.len(), original.size());
+        let decoded: GenericStruct<u16> = decode(&encoded).await.unwrap();
+
+        assert_eq!(original, decoded);
+    });
+}
+
+#[test]
+fn decode_in_place_test() {
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    struct Message {
+        id: u32,
+        values: Vec<f32>,
+        tags: Vec<String>,
+    }
+
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    struct Wrapper(Message, String);
+
+    executor::block_on(async {
+        let first = Wrapper(
+            Message {
+                id: 1,
+                values: vec![1.0, 2.0, 3.0],
+                tags: vec!["first".to_owned(), "second".to_owned()],
+            },
+            "hello".to_owned(),
+        );
+        let second = Wrapper(
+            Message {
+                id: 2,
+                values: vec![4.0, 5.0],
+                tags: vec!["third".to_owned()],
+            },
+            "world".to_owned(),
+        );
+
+        let mut decoded: Wrapper = decode(&encode(&first).await).await.unwrap();
+        assert_eq!(first, decoded);
+
+        let values_ptr = decoded.0.values.as_ptr();
+        let tag_ptr = decoded.0.tags[0].as_ptr();
+
+        let encoded = encode(&second).await;
+        decoded
+            .decode_in_place(nimble::config(), &encoded[..])
+            .await
+            .unwrap();
+
+        assert_eq!(second, decoded);
+        assert_eq!(values_ptr, decoded.0.values.as_ptr());
+        assert_eq!(tag_ptr, decoded.0.tags[0].as_ptr());
+    });
+}
+
+#[test]
+fn max_size_struct_test() {
+    #[derive(Debug, PartialEq, Encode, Decode, MaxSize)]
+    struct Header<T> {
+        id: u32,
+        flags: Option<u8>,
+        value: T,
+        pair: ([u16; 4], bool),
+    }
+
+    #[derive(MaxSize)]
+    struct Empty;
+
+    assert_eq!(0, Empty::MAX_SIZE);
+    assert_eq!(4 + 2 + 8 + 9, Header::<u64>::MAX_SIZE);
+    assert_eq!(4 + 2 + 2 + 9, Header::<Option<u8>>::MAX_SIZE);
+
+    let original = Header {
+        id: 1,
+        flags: Some(2),
+        value: u64::MAX,
+        pair: ([1, 2, 3, 4], true),
+    };
+    assert_eq!(Header::<u64>::MAX_SIZE, original.size());
+
+    executor::block_on(async {
+        let encoded = encode(&original).await;
+        assert!(encoded.len() <= Header::<u64>::MAX_SIZE);
+        let decoded: Header<u64> = decode(&encoded).await.unwrap();
+
+        assert_eq!(original, decoded);
+    });
+}
+
+#[test]
+fn fixed_size_struct_test() {
+    #[derive(Debug, Clone, PartialEq, Encode, Decode)]
+    struct Point<T> {
+        x: T,
+        y: T,
+        tag: [u8; 2],
+    }
+
+    #[derive(Encode)]
+    struct Named {
+        point: Point<u32>,
+        name: String,
+    }
+
+    assert_eq!(Some(10), Point::<u32>::FIXED_SIZE);
+    assert_eq!(None, Point::<String>::FIXED_SIZE);
+    assert_eq!(None, Named::FIXED_SIZE);
+
+    let original = vec![
+        Point {
+            x: 1u32,
+            y: 2,
+            tag: [3, 4],
+        };
+        100
+    ];
+    assert_eq!(1 + 10 * 100, original.size());
+
+    executor::block_on(async {
+        let encoded = encode(&original).await;
+        assert_eq!(encoded.len(), original.size());
+        let decoded: Vec<Point<u32>> = decode(&encoded).await.unwrap();
+
+        assert_eq!(original, decoded);
+    });
+}
+
+#[test]
+fn non_send_field_test() {
+    use std::rc::Rc;
+
+    // `decode_in_place()` of derived structs must not require fields to be `Send`
+    #[derive(Debug, PartialEq, Decode)]
+    struct NonSend {
+        a: Rc<u8>,
+    }
+
+    let decoded = NonSend::decode_from_slice(nimble::config(), &mut &[42][..]).unwrap();
+    assert_eq!(NonSend { a: Rc::new(42) }, decoded);
+}
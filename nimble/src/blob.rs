@@ -0,0 +1,208 @@
+use alloc::vec;
+use core::{
+    cmp,
+    convert::TryFrom,
+    future::poll_fn,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+
+use crate::{
+    io::{Read, Write, WriteExt},
+    Config, Decode, Encode, Error, Result, VarInt,
+};
+
+/// Size of buffer used to copy bytes from reader to writer while encoding a [`Blob`]
+const COPY_BUF_SIZE: usize = 64 * 1024;
+
+/// Byte payload of known length which is streamed from (or to) a reader instead of being held in memory
+///
+/// A blob is encoded in the same format as `Vec<u8>` (`VarInt` length followed by the bytes), so a message whose last
+/// field is a `Vec<u8>` can be written as the other fields followed by a blob (and vice versa). Encoding copies exactly
+/// `len` bytes from reader to writer and decoding returns a blob whose reader is a [`BlobReader`] over the payload.
+///
+/// A blob does not implement `Encode` and `Decode` because its payload is copied from (or left in) a reader instead of
+/// being encoded from memory. Encode the other fields of a message first and then write the blob with
+/// [`encode_to`](#method.encode_to). On the other side, decode the other fields and then the blob with
+/// [`Blob::decode_from`].
+#[derive(Debug, Clone, Copy)]
+pub struct Blob<R> {
+    len: u64,
+    reader: R,
+}
+
+impl<R> Blob<R> {
+    /// Creates a new blob of `len` bytes which are read from `reader`
+    #[inline]
+    pub const fn new(len: u64, reader: R) -> Self {
+        Self { len, reader }
+    }
+
+    /// Returns length of payload in bytes
+    #[inline]
+    pub const fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns `true` if payload is empty
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns a reference to the inner reader
+    #[inline]
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Returns a mutable reference to the inner reader
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    /// Consumes the blob and returns the inner reader
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: Read + Unpin + Send> Blob<R> {
+    /// Writes length of payload followed by `len` bytes copied from reader and returns the number of bytes written
+    ///
+    /// Returns [`Error::UnexpectedEof`](enum.Error.html#variant.UnexpectedEof) if reader reaches end of source before
+    /// `len` bytes are copied.
+    pub async fn encode_to<W>(&mut self, config: &Config, mut writer: W) -> Result<usize>
+    where
+        W: Write + Unpin + Send,
+    {
+        let mut written = VarInt::from(self.len)
+            .encode_to(config, &mut writer)
+            .await?;
+
+        let mut remaining = self.len;
+        let mut buf = vec![
+            0u8;
+            usize::try_from(remaining)
+                .map_or(COPY_BUF_SIZE, |len| cmp::min(len, COPY_BUF_SIZE))
+        ];
+
+        while remaining > 0 {
+            let limit =
+                usize::try_from(remaining).map_or(buf.len(), |len| cmp::min(len, buf.len()));
+            let read =
+                poll_fn(|cx| Pin::new(&mut self.reader).poll_read(cx, &mut buf[..limit])).await?;
+
+            if read == 0 {
+                return Err(Error::UnexpectedEof);
+            }
+
+            writer.write_all(&buf[..read]).await?;
+
+            remaining -= read as u64;
+            written += read;
+        }
+
+        Ok(written)
+    }
+}
+
+impl<R: Read + Unpin + Send> Blob<BlobReader<R>> {
+    /// Decodes length of payload from reader and returns a blob whose reader reads the payload from `reader`
+    ///
+    /// Payload is not read until the returned [`BlobReader`] is read.
+    pub async fn decode_from(config: &Config, mut reader: R) -> Result<Self> {
+        let len = u64::try_from(VarInt::decode_from(config, &mut reader).await?)?;
+
+        Ok(Self::new(
+            len,
+            BlobReader {
+                reader,
+                remaining: len,
+            },
+        ))
+    }
+}
+
+/// Reader over the payload of a decoded [`Blob`] which reads at most the length of payload from the underlying reader
+///
+/// Returns [`Error::UnexpectedEof`](enum.Error.html#variant.UnexpectedEof) if the underlying reader reaches end of
+/// source before the whole payload is read. `BlobReader` also implements `futures`' and `tokio`'s `AsyncRead` (with
+/// `"futures"` and `"tokio"` features respectively).
+#[derive(Debug)]
+pub struct BlobReader<R> {
+    reader: R,
+    remaining: u64,
+}
+
+impl<R> BlobReader<R> {
+    /// Returns the number of bytes of payload which are yet to be read
+    #[inline]
+    pub const fn remaining(&self) -> u64 {
+        self.remaining
+    }
+
+    /// Returns a reference to the underlying reader
+    #[inline]
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Consumes the blob reader and returns the underlying reader (positioned after the bytes of payload read so far)
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: Read + Unpin> Read for BlobReader<R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<Result<usize>> {
+        if self.remaining == 0 || buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        let limit =
+            usize::try_from(self.remaining).map_or(buf.len(), |len| cmp::min(len, buf.len()));
+        let read = ready!(Pin::new(&mut self.reader).poll_read(cx, &mut buf[..limit]))?;
+
+        if read == 0 {
+            return Poll::Ready(Err(Error::UnexpectedEof));
+        }
+
+        self.remaining -= read as u64;
+
+        Poll::Ready(Ok(read))
+    }
+}
+
+#[cfg(feature = "futures")]
+impl<R: Read + Unpin> futures_util::io::AsyncRead for BlobReader<R> {
+    #[inline]
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        Read::poll_read(self, cx, buf).map_err(Into::into)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<R: Read + Unpin> tokio::io::AsyncRead for BlobReader<R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let read = ready!(Read::poll_read(self, cx, buf.initialize_unfilled()))?;
+        buf.advance(read);
+
+        Poll::Ready(Ok(()))
+    }
+}
@@ -0,0 +1,93 @@
+//! Blocking adapters for driving encoding/decoding over `std::io` types
+use core::{
+    future::Future,
+    pin::{pin, Pin},
+    task::{Context, Poll, Waker},
+};
+use std::{
+    io::{self, ErrorKind},
+    sync::Arc,
+    task::Wake,
+    thread::{self, Thread},
+};
+
+use crate::{
+    io::{Read, Write},
+    Result,
+};
+
+/// Adapter implementing [`Read`](../io/trait.Read.html) and [`Write`](../io/trait.Write.html) for blocking `std::io`
+/// types (its futures never return `Poll::Pending`)
+pub(crate) struct StdIo<T>(pub(crate) T);
+
+impl<T: io::Read + Unpin> Read for StdIo<T> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<Result<usize>> {
+        let this = self.get_mut();
+
+        loop {
+            match this.0.read(buf) {
+                Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+                result => return Poll::Ready(result.map_err(Into::into)),
+            }
+        }
+    }
+}
+
+impl<T: io::Write + Unpin> Write for StdIo<T> {
+    fn poll_write(self: Pin<&mut Self>, _cx: &mut Context<'_>, buf: &[u8]) -> Poll<Result<usize>> {
+        let this = self.get_mut();
+
+        loop {
+            match this.0.write(buf) {
+                Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+                result => return Poll::Ready(result.map_err(Into::into)),
+            }
+        }
+    }
+
+    #[inline]
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Poll::Ready(self.get_mut().0.flush().map_err(Into::into))
+    }
+
+    #[inline]
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.poll_flush(cx)
+    }
+}
+
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    #[inline]
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+
+    #[inline]
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Drives a future to completion on current thread
+///
+/// Futures created over [`StdIo`] complete on first poll. Current thread is only parked when a hand-written `Encode` or
+/// `Decode` implementation awaits on something else.
+pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
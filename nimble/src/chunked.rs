@@ -0,0 +1,317 @@
+use alloc::vec::Vec;
+use core::{
+    borrow::Borrow,
+    convert::TryFrom,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
+
+#[cfg(feature = "bytes")]
+use bytes::Bytes;
+#[cfg(feature = "futures")]
+use futures_util::stream::{Stream, StreamExt};
+
+use crate::{
+    encode::elements_size,
+    io::{Read, Write, WriteExt},
+    Config, Decode, Encode, Result, VarInt,
+};
+
+/// Default maximum number of elements in a chunk
+const DEFAULT_MAX_CHUNK_LEN: usize = 1024;
+
+/// Number of buffered bytes after which [`ChunkedEncoder`] writes a chunk (irrespective of number of elements)
+const MAX_CHUNK_BYTES: usize = 64 * 1024;
+
+/// Sequence encoded as chunks (for sequences whose length is not known in advance)
+///
+/// Elements are encoded as a series of chunks (`VarInt` number of elements followed by the elements) terminated by an
+/// empty chunk. Use [`ChunkedEncoder`](struct.ChunkedEncoder.html) to write elements as they are produced and
+/// `nimble::stream::decode_chunked_stream()` (with `"futures"` feature) to read them one at a time.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Chunked<T>(pub Vec<T>);
+
+impl<T> Chunked<T> {
+    /// Returns the inner `Vec`
+    #[inline]
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T> From<Vec<T>> for Chunked<T> {
+    #[inline]
+    fn from(vec: Vec<T>) -> Self {
+        Self(vec)
+    }
+}
+
+impl<T> Deref for Chunked<T> {
+    type Target = Vec<T>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Chunked<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+/// Returns `VarInt` length prefix of a chunk
+#[inline]
+fn chunk_len(len: usize) -> VarInt {
+    VarInt::try_from(len).expect(
+        "Failed to convert usize to u128. Log an issue on nimble's GitHub repository with backtrace.",
+    )
+}
+
+impl<T> Encode for Chunked<T>
+where
+    T: Encode + Sync,
+{
+    fn size(&self) -> usize {
+        self.0
+            .chunks(DEFAULT_MAX_CHUNK_LEN)
+            .map(|chunk| chunk_len(chunk.len()).size() + elements_size(chunk.len(), chunk.iter()))
+            .sum::<usize>()
+            + chunk_len(0).size()
+    }
+
+    async fn encode_to<W>(&self, config: &Config, mut writer: W) -> Result<usize>
+    where
+        W: Write + Unpin + Send,
+    {
+        let mut encoded = 0;
+
+        for chunk in self.0.chunks(DEFAULT_MAX_CHUNK_LEN) {
+            encoded += chunk_len(chunk.len())
+                .encode_to(config, &mut writer)
+                .await?;
+            encoded += T::encode_slice_to(chunk, config, &mut writer).await?;
+        }
+
+        encoded += chunk_len(0).encode_to(config, &mut writer).await?;
+
+        Ok(encoded)
+    }
+
+    fn encode_to_vec(&self, config: &Config, buf: &mut Vec<u8>) -> usize {
+        let mut encoded = 0;
+
+        for chunk in self.0.chunks(DEFAULT_MAX_CHUNK_LEN) {
+            encoded += chunk_len(chunk.len()).encode_to_vec(config, buf);
+            encoded += T::encode_slice_to_vec(chunk, config, buf);
+        }
+
+        encoded + chunk_len(0).encode_to_vec(config, buf)
+    }
+}
+
+impl<T> Decode for Chunked<T>
+where
+    T: Decode + Send,
+{
+    async fn decode_from<R>(config: &Config, mut reader: R) -> Result<Self>
+    where
+        R: Read + Unpin + Send,
+    {
+        let mut values = Vec::new();
+
+        loop {
+            let len = usize::try_from(VarInt::decode_from(config, &mut reader).await?)?;
+
+            if len == 0 {
+                return Ok(Self(values));
+            }
+
+            values.append(&mut T::decode_vec_from(len, config, &mut reader).await?);
+        }
+    }
+
+    fn decode_from_slice(config: &Config, bytes: &mut &[u8]) -> Result<Self> {
+        let mut values = Vec::new();
+
+        loop {
+            let len = usize::try_from(VarInt::decode_from_slice(config, bytes)?)?;
+
+            if len == 0 {
+                return Ok(Self(values));
+            }
+
+            values.append(&mut T::decode_vec_from_slice(len, config, bytes)?);
+        }
+    }
+
+    #[cfg(feature = "bytes")]
+    fn decode_from_bytes(config: &Config, bytes: &mut Bytes) -> Result<Self> {
+        let mut values = Vec::new();
+
+        loop {
+            let len = usize::try_from(VarInt::decode_from_bytes(config, bytes)?)?;
+
+            if len == 0 {
+                return Ok(Self(values));
+            }
+
+            values.append(&mut T::decode_vec_from_bytes(len, config, bytes)?);
+        }
+    }
+}
+
+/// Encoder which writes a sequence of unknown length in the same format as [`Chunked<T>`](struct.Chunked.html)
+///
+/// Encoded elements are buffered in memory and written to writer as a chunk once the buffer holds
+/// [`max_chunk_len`](#method.max_chunk_len) elements (1024 by default) or 64 KiB of encoded bytes, so memory usage stays
+/// bounded however many elements are encoded. [`write_chunk`](#method.write_chunk) writes buffered elements right away
+/// (for example, when a producer is idle). [`finish`](#method.finish) must be called to terminate the sequence.
+#[derive(Debug)]
+pub struct ChunkedEncoder<T: ?Sized, W> {
+    writer: W,
+    config: Config,
+    max_chunk_len: usize,
+    buf: Vec<u8>,
+    buffered: usize,
+    written: usize,
+    _marker: PhantomData<fn(&T)>,
+}
+
+impl<T: ?Sized, W> ChunkedEncoder<T, W> {
+    /// Creates a new encoder using default configuration
+    #[inline]
+    pub fn new(writer: W) -> Self {
+        Self::with_config(Config::default(), writer)
+    }
+
+    /// Creates a new encoder using given configuration
+    #[inline]
+    pub fn with_config(config: Config, writer: W) -> Self {
+        Self {
+            writer,
+            config,
+            max_chunk_len: DEFAULT_MAX_CHUNK_LEN,
+            buf: Vec::new(),
+            buffered: 0,
+            written: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns maximum number of elements in a chunk
+    #[inline]
+    pub fn max_chunk_len(&self) -> usize {
+        self.max_chunk_len
+    }
+
+    /// Sets maximum number of elements in a chunk
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_chunk_len` is `0` (an empty chunk terminates the sequence).
+    #[inline]
+    pub fn set_max_chunk_len(&mut self, max_chunk_len: usize) {
+        assert!(max_chunk_len > 0, "Maximum chunk length must be non-zero");
+        self.max_chunk_len = max_chunk_len;
+    }
+
+    /// Returns the number of elements buffered in encoder which are not yet written to writer
+    #[inline]
+    pub fn buffered(&self) -> usize {
+        self.buffered
+    }
+}
+
+impl<T, W> ChunkedEncoder<T, W>
+where
+    T: Encode + ?Sized,
+    W: Write + Unpin + Send,
+{
+    /// Buffers the next element of the sequence (writing a chunk if the buffer is full)
+    pub async fn encode(&mut self, value: &T) -> Result<()> {
+        value.encode_to_vec(&self.config, &mut self.buf);
+        self.buffered += 1;
+
+        if self.buffered >= self.max_chunk_len || self.buf.len() >= MAX_CHUNK_BYTES {
+            self.write_chunk().await?;
+        }
+
+        Ok(())
+    }
+
+    /// Buffers all the elements of an iterator (writing chunks as the buffer fills up)
+    pub async fn encode_iter<I>(&mut self, iter: I) -> Result<()>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<T>,
+    {
+        for value in iter {
+            self.encode(value.borrow()).await?;
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "futures")]
+    /// Buffers all the elements of a `futures`' `Stream` (writing chunks as the buffer fills up)
+    pub async fn encode_stream<S>(&mut self, mut stream: S) -> Result<()>
+    where
+        S: Stream + Unpin,
+        S::Item: Borrow<T>,
+    {
+        while let Some(value) = stream.next().await {
+            self.encode(value.borrow()).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes buffered elements (if any) as a chunk
+    ///
+    /// Writer is flushed if [`Config::flush`](struct.Config.html#method.flush) is `true`.
+    pub async fn write_chunk(&mut self) -> Result<()> {
+        self.write_buf().await?;
+
+        if self.config.flush() {
+            self.writer.flush().await?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes buffered elements and terminates the sequence, returning the total number of bytes written
+    pub async fn finish(mut self) -> Result<usize> {
+        self.write_buf().await?;
+
+        self.written += chunk_len(0)
+            .encode_to(&self.config, &mut self.writer)
+            .await?;
+
+        if self.config.flush() {
+            self.writer.flush().await?;
+        }
+
+        Ok(self.written)
+    }
+
+    /// Writes buffered elements (if any) as a chunk without flushing writer
+    async fn write_buf(&mut self) -> Result<()> {
+        if self.buffered == 0 {
+            return Ok(());
+        }
+
+        self.written += chunk_len(self.buffered)
+            .encode_to(&self.config, &mut self.writer)
+            .await?;
+        self.writer.write_all(&self.buf).await?;
+        self.written += self.buf.len();
+
+        self.buf.clear();
+        self.buffered = 0;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,147 @@
+//! `tokio-util` codec for encoding/decoding length delimited frames of values
+use alloc::vec::Vec;
+use core::{convert::TryFrom, marker::PhantomData};
+
+use bytes::{Buf, BufMut, BytesMut};
+use tokio_util::codec::{Decoder, Encoder};
+
+use crate::{Config, Decode, Encode, Error, Result, DEFAULT_MAX_VALUE_SIZE};
+
+/// Number of bytes in length prefix of a frame
+const LENGTH_PREFIX_LEN: usize = core::mem::size_of::<u32>();
+
+/// Codec for using values implementing [`Encode`](../trait.Encode.html) and [`Decode`](../trait.Decode.html) with
+/// `tokio-util`'s `Framed`, `FramedRead` and `FramedWrite`
+///
+/// Each value is sent in a frame consisting of the size of encoded value (as a big endian `u32`, which is compatible with
+/// default configuration of `tokio-util`'s `LengthDelimitedCodec`) followed by the encoded value. Encoding or decoding a
+/// frame larger than maximum frame size fails with [`Error::FrameTooLarge`](../enum.Error.html#variant.FrameTooLarge) and
+/// decoding a frame whose value does not span the whole frame fails with
+/// [`Error::TrailingBytes`](../enum.Error.html#variant.TrailingBytes).
+#[derive(Debug)]
+pub struct NimbleCodec<T> {
+    config: Config,
+    max_frame_size: usize,
+    buf: Vec<u8>,
+    _marker: PhantomData<fn(T) -> T>,
+}
+
+impl<T> NimbleCodec<T> {
+    /// Creates a new codec using default configuration and maximum frame size
+    /// ([`DEFAULT_MAX_VALUE_SIZE`](../constant.DEFAULT_MAX_VALUE_SIZE.html))
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_config(Config::default())
+    }
+
+    /// Creates a new codec using given configuration and default maximum frame size
+    /// ([`DEFAULT_MAX_VALUE_SIZE`](../constant.DEFAULT_MAX_VALUE_SIZE.html))
+    #[inline]
+    pub fn with_config(config: Config) -> Self {
+        Self {
+            config,
+            max_frame_size: DEFAULT_MAX_VALUE_SIZE,
+            buf: Vec::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns configuration used by codec
+    #[inline]
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Returns maximum size of a frame's payload
+    #[inline]
+    pub fn max_frame_size(&self) -> usize {
+        self.max_frame_size
+    }
+
+    /// Sets maximum size of a frame's payload (can be at most `u32::MAX`)
+    #[inline]
+    pub fn with_max_frame_size(mut self, max_frame_size: usize) -> Self {
+        self.max_frame_size = max_frame_size;
+        self
+    }
+
+    /// Returns an error if `size` is larger than maximum frame size
+    fn check_frame_size(&self, size: usize) -> Result<()> {
+        if size > self.max_frame_size || u32::try_from(size).is_err() {
+            Err(Error::FrameTooLarge {
+                size,
+                max: self.max_frame_size,
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<T> Default for NimbleCodec<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for NimbleCodec<T> {
+    fn clone(&self) -> Self {
+        Self {
+            config: self.config.clone(),
+            max_frame_size: self.max_frame_size,
+            buf: Vec::new(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Encode> Encoder<T> for NimbleCodec<T> {
+    type Error = Error;
+
+    fn encode(&mut self, item: T, dst: &mut BytesMut) -> Result<()> {
+        self.check_frame_size(item.size())?;
+
+        self.buf.clear();
+        item.encode_to_vec(&self.config, &mut self.buf);
+        self.check_frame_size(self.buf.len())?;
+
+        dst.reserve(LENGTH_PREFIX_LEN + self.buf.len());
+        dst.put_u32(self.buf.len() as u32);
+        dst.extend_from_slice(&self.buf);
+
+        Ok(())
+    }
+}
+
+impl<T: Decode> Decoder for NimbleCodec<T> {
+    type Item = T;
+    type Error = Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<T>> {
+        if src.len() < LENGTH_PREFIX_LEN {
+            return Ok(None);
+        }
+
+        let mut prefix = [0u8; LENGTH_PREFIX_LEN];
+        prefix.copy_from_slice(&src[..LENGTH_PREFIX_LEN]);
+        let size = usize::try_from(u32::from_be_bytes(prefix))?;
+        self.check_frame_size(size)?;
+
+        if src.len() < LENGTH_PREFIX_LEN + size {
+            src.reserve(LENGTH_PREFIX_LEN + size - src.len());
+            return Ok(None);
+        }
+
+        src.advance(LENGTH_PREFIX_LEN);
+        let mut frame = src.split_to(size).freeze();
+
+        let value = T::decode_from_bytes(&self.config, &mut frame)?;
+
+        if !frame.is_empty() {
+            return Err(Error::TrailingBytes(frame.len()));
+        }
+
+        Ok(Some(value))
+    }
+}
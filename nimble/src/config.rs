@@ -1,13 +1,30 @@
+use alloc::vec::Vec;
+use core::{cmp, future::poll_fn};
+
+#[cfg(feature = "bytes")]
+use bytes::{BufMut, Bytes};
+
+#[cfg(feature = "std")]
+use crate::blocking::{block_on, StdIo};
+#[cfg(feature = "bytes")]
+use crate::io::BufMutWriter;
 use crate::{
-    io::{Read, Write},
-    Decode, Encode, Result,
+    io::{poll_in_memory, BufWriter, Read, Write, WriteExt},
+    Decode, DecodeBorrowed, Encode, Error, Result,
 };
 
+/// Maximum number of bytes staged in memory by [`Config::encode_to`] before writing them to writer
+const MAX_STAGING_CAPACITY: usize = 64 * 1024;
+
 /// Encoding/decoding configuration
+///
+/// Configuration is built from [`Config::default()`](#method.default) using `with_*` methods, for example,
+/// `Config::default().with_endianness(Endianness::BigEndian)`.
 #[derive(Debug, Clone)]
 pub struct Config {
     /// Denotes endianness of encoded bytes
     pub endianness: Endianness,
+    flush: bool,
 }
 
 impl Config {
@@ -16,34 +33,119 @@ impl Config {
     pub const fn new_default() -> Self {
         Self {
             endianness: Endianness::new_default(),
+            flush: false,
         }
     }
 
+    #[inline]
+    /// Sets endianness of encoded bytes
+    pub const fn with_endianness(mut self, endianness: Endianness) -> Self {
+        self.endianness = endianness;
+        self
+    }
+
+    #[inline]
+    /// Sets whether writer is flushed after writing encoded bytes (defaults to `false`)
+    pub const fn with_flush(mut self, flush: bool) -> Self {
+        self.flush = flush;
+        self
+    }
+
+    #[inline]
+    /// Returns `true` if writer is flushed after writing encoded bytes in [`encode_to`](#method.encode_to)
+    pub const fn flush(&self) -> bool {
+        self.flush
+    }
+
     /// Encodes a value in a `Vec`
     pub async fn encode<E: Encode + ?Sized>(&self, value: &E) -> Vec<u8> {
         let mut bytes = Vec::with_capacity(value.size());
-        // This will never fail because `encode_to()` returns `Err` only then there is an IO error which cannot happen when
-        // writing to a `Vec`
-        let _ = value.encode_to(self, &mut bytes).await.expect(
-            "Failed to encode value. Log an issue on nimble's GitHub repository with backtrace.",
-        );
+        value.encode_to_vec(self, &mut bytes);
         bytes
     }
 
-    #[inline]
+    /// Writes encoded byte array into the start of `buf` (without allocating) and returns the number of bytes written
+    ///
+    /// Returns [`Error::BufferTooSmall`](enum.Error.html#variant.BufferTooSmall) (without writing anything) if `buf` is
+    /// smaller than `Encode::size()` of `value`.
+    pub fn encode_into_slice<E: Encode + ?Sized>(
+        &self,
+        value: &E,
+        buf: &mut [u8],
+    ) -> Result<usize> {
+        let needed = value.size();
+
+        if buf.len() < needed {
+            return Err(Error::BufferTooSmall { needed });
+        }
+
+        poll_in_memory(value.encode_to(self, &mut buf[..needed]))
+    }
+
     /// Writes encoded byte array to writer and returns the number of bytes written
+    ///
+    /// Encoded bytes are staged in an internal buffer (sized from `Encode::size()`, up to 64 KiB) and written to writer
+    /// in large chunks. Writer is flushed at the end if [`flush`](#method.flush) is `true`.
     pub async fn encode_to<E: Encode + ?Sized, W: Write + Unpin + Send>(
         &self,
         value: &E,
         writer: W,
     ) -> Result<usize> {
-        value.encode_to(self, writer).await
+        let capacity = cmp::min(value.size(), MAX_STAGING_CAPACITY);
+        let mut writer = BufWriter::with_capacity(capacity, writer);
+
+        let written = value.encode_to(self, &mut writer).await?;
+
+        if self.flush {
+            writer.flush().await?;
+        } else {
+            poll_fn(|cx| writer.poll_flush_buf(cx)).await?;
+        }
+
+        Ok(written)
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    /// Writes encoded byte array to a blocking `std::io::Write` and returns the number of bytes written
+    pub fn encode_to_writer<E: Encode + ?Sized, W: std::io::Write + Send>(
+        &self,
+        value: &E,
+        mut writer: W,
+    ) -> Result<usize> {
+        block_on(self.encode_to(value, StdIo(&mut writer)))
+    }
+
+    #[cfg(feature = "bytes")]
+    #[inline]
+    /// Writes encoded byte array into a `BufMut` and returns the number of bytes written
+    ///
+    /// Returns [`Error::WriteZero`](enum.Error.html#variant.WriteZero) if `buf` does not have enough remaining capacity.
+    pub fn encode_to_buf<E: Encode + ?Sized, B: BufMut + Send + ?Sized>(
+        &self,
+        value: &E,
+        buf: &mut B,
+    ) -> Result<usize> {
+        poll_in_memory(value.encode_to(self, BufMutWriter::new(buf)))
     }
 
     #[inline]
     /// Decodes a value from bytes
     pub async fn decode<D: Decode, T: AsRef<[u8]>>(&self, bytes: T) -> Result<D> {
-        self.decode_from(&mut bytes.as_ref()).await
+        D::decode_from_slice(self, &mut bytes.as_ref())
+    }
+
+    #[cfg(feature = "bytes")]
+    #[inline]
+    /// Decodes a value from `Bytes` (`Bytes` values in decoded value are zero-copy slices of `bytes`)
+    pub fn decode_bytes<D: Decode>(&self, mut bytes: Bytes) -> Result<D> {
+        D::decode_from_bytes(self, &mut bytes)
+    }
+
+    #[inline]
+    /// Decodes a value which can borrow from `bytes` (for example, `&str` or `&[u8]`) without copying
+    pub fn decode_borrowed<'de, D: DecodeBorrowed<'de>>(&self, bytes: &'de [u8]) -> Result<D> {
+        D::decode_borrowed(self, &mut &*bytes)
     }
 
     #[inline]
@@ -51,6 +153,16 @@ impl Config {
     pub async fn decode_from<D: Decode, R: Read + Unpin + Send>(&self, reader: R) -> Result<D> {
         D::decode_from(self, reader).await
     }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    /// Decodes values from a blocking `std::io::Read`
+    pub fn decode_from_reader<D: Decode, R: std::io::Read + Send>(
+        &self,
+        mut reader: R,
+    ) -> Result<D> {
+        block_on(D::decode_from(self, StdIo(&mut reader)))
+    }
 }
 
 impl Default for Config {
@@ -75,6 +187,15 @@ impl Endianness {
     pub const fn new_default() -> Self {
         Self::LittleEndian
     }
+
+    #[inline]
+    /// Returns `true` if this is the endianness of target platform (values can be encoded by copying their bytes)
+    pub(crate) const fn is_native(self) -> bool {
+        match self {
+            Self::LittleEndian => cfg!(target_endian = "little"),
+            Self::BigEndian => cfg!(target_endian = "big"),
+        }
+    }
 }
 
 impl Default for Endianness {
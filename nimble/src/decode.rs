@@ -1,49 +1,360 @@
+use alloc::{
+    borrow::{Cow, ToOwned},
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet, BinaryHeap, LinkedList, VecDeque},
+    ffi::CString,
+    rc::Rc,
+    string::String,
+    sync::Arc,
+    vec,
+    vec::Vec,
+};
+#[cfg(feature = "std")]
+use core::hash::{BuildHasher, Hash};
 use core::{
+    cell::RefCell,
+    cmp,
     convert::TryFrom,
-    hash::{BuildHasher, Hash},
+    future::Future,
     marker::PhantomData,
+    mem,
     num::{
         NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
         NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
     },
 };
-use std::{
-    borrow::Cow,
-    collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque},
-    ffi::CString,
-    rc::Rc,
-    sync::Arc,
-};
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
 
-use arrayvec::ArrayVec;
+#[cfg(feature = "bytes")]
+use bytes::{Buf, Bytes, BytesMut};
 
 use crate::{
-    async_trait,
-    io::{Read, ReadExt},
+    io::{poll_in_memory, take_bytes, Read, ReadExt},
+    marker::{BoxFuture, DynRead},
     Config, Endianness, Error, Result, VarInt,
 };
 
-#[async_trait]
 /// Trait for decoding values
 pub trait Decode: Sized {
     /// Decodes values from reader
     ///
+    /// Implementations can use `async fn` syntax as long as the returned future is `Send` (see `LocalDecode` for
+    /// single-threaded counterpart).
+    ///
     /// ## Equivalent to:
     ///
     /// ```rust,ignore
-    /// async fn decode_from<R>(reader: R) -> Result<Self>
+    /// async fn decode_from<R>(config: &Config, reader: R) -> Result<Self>
     /// where
     ///     R: Read + Unpin + Send
     /// ```
-    async fn decode_from<R>(config: &Config, reader: R) -> Result<Self>
+    fn decode_from<R>(config: &Config, reader: R) -> impl Future<Output = Result<Self>> + Send
     where
         R: Read + Unpin + Send;
+
+    /// Decodes value from the start of `bytes` synchronously and advances `bytes` past the decoded value
+    ///
+    /// Consumes the same bytes as [`decode_from`](#tymethod.decode_from). Default implementation drives `decode_from` to
+    /// completion without an executor (reading from a slice never blocks). Implementations should override this method
+    /// to avoid going through `async` machinery for in-memory decoding.
+    fn decode_from_slice(config: &Config, bytes: &mut &[u8]) -> Result<Self> {
+        poll_in_memory(Self::decode_from(config, bytes))
+    }
+
+    /// Decodes value from the start of `bytes` and advances `bytes` past the decoded value
+    ///
+    /// `Bytes` values (including the ones nested in collections and derived types) are decoded as zero-copy slices of
+    /// `bytes`. Default implementation decodes value using [`decode_from_slice`](#method.decode_from_slice).
+    #[cfg(feature = "bytes")]
+    fn decode_from_bytes(config: &Config, bytes: &mut Bytes) -> Result<Self> {
+        decode_from_bytes_with(bytes, |slice| Self::decode_from_slice(config, slice))
+    }
+
+    /// Decodes a value from reader into `self`, reusing existing allocations of `self` where possible
+    ///
+    /// Default implementation decodes a new value and replaces `self` with it. Collection types and derived structs
+    /// override this method to clear and refill their existing buffers. If decoding fails, `self` is left in a valid but
+    /// unspecified state.
+    ///
+    /// ## Equivalent to:
+    ///
+    /// ```rust,ignore
+    /// async fn decode_in_place<R>(&mut self, config: &Config, reader: R) -> Result<()>
+    /// where
+    ///     Self: Send,
+    ///     R: Read + Unpin + Send
+    /// ```
+    fn decode_in_place<R>(
+        &mut self,
+        config: &Config,
+        reader: R,
+    ) -> impl Future<Output = Result<()>> + Send
+    where
+        Self: Send,
+        R: Read + Unpin + Send,
+    {
+        async move {
+            *self = Self::decode_from(config, reader).await?;
+            Ok(())
+        }
+    }
+
+    /// Decodes `len` values (encoded without length prefix) from reader
+    ///
+    /// Used by sequence types to decode their elements. Default implementation decodes values one by one. Types which
+    /// can decode multiple values at once (for example, `u8`) override this method.
+    fn decode_vec_from<R>(
+        len: usize,
+        config: &Config,
+        mut reader: R,
+    ) -> impl Future<Output = Result<Vec<Self>>> + Send
+    where
+        Self: Send,
+        R: Read + Unpin + Send,
+    {
+        async move {
+            let mut values = Vec::with_capacity(reader_capacity::<Self>(len));
+
+            for _ in 0..len {
+                values.push(Self::decode_from(config, &mut reader).await?);
+            }
+
+            Ok(values)
+        }
+    }
+
+    /// Decodes `len` values (encoded without length prefix) from the start of `bytes` synchronously and advances `bytes`
+    /// past the decoded values
+    fn decode_vec_from_slice(len: usize, config: &Config, bytes: &mut &[u8]) -> Result<Vec<Self>> {
+        let mut values = Vec::with_capacity(slice_capacity(len, bytes));
+
+        for _ in 0..len {
+            values.push(Self::decode_from_slice(config, bytes)?);
+        }
+
+        Ok(values)
+    }
+
+    /// Decodes `len` values (encoded without length prefix) from the start of `bytes` and advances `bytes` past the
+    /// decoded values
+    #[cfg(feature = "bytes")]
+    fn decode_vec_from_bytes(len: usize, config: &Config, bytes: &mut Bytes) -> Result<Vec<Self>> {
+        let mut values = Vec::with_capacity(slice_capacity(len, bytes));
+
+        for _ in 0..len {
+            values.push(Self::decode_from_bytes(config, bytes)?);
+        }
+
+        Ok(values)
+    }
+
+    /// Decodes `len` values (encoded without length prefix) from reader into `vec`, reusing existing elements and
+    /// allocation of `vec`
+    fn decode_vec_in_place<R>(
+        vec: &mut Vec<Self>,
+        len: usize,
+        config: &Config,
+        mut reader: R,
+    ) -> impl Future<Output = Result<()>> + Send
+    where
+        Self: Send,
+        R: Read + Unpin + Send,
+    {
+        async move {
+            vec.truncate(len);
+
+            for value in vec.iter_mut() {
+                value.decode_in_place(config, &mut reader).await?;
+            }
+
+            for _ in vec.len()..len {
+                vec.push(Self::decode_from(config, &mut reader).await?);
+            }
+
+            Ok(())
+        }
+    }
+}
+
+/// Decodes a value from `bytes` using `decode` (which decodes from a slice) and advances `bytes` past the decoded value
+#[cfg(feature = "bytes")]
+fn decode_from_bytes_with<T>(
+    bytes: &mut Bytes,
+    decode: impl FnOnce(&mut &[u8]) -> Result<T>,
+) -> Result<T> {
+    let mut slice = &bytes[..];
+    let value = decode(&mut slice)?;
+
+    let consumed = bytes.len() - slice.len();
+    bytes.advance(consumed);
+
+    Ok(value)
+}
+
+/// Maximum number of bytes pre-allocated for a sequence whose length is decoded from a reader (buffers grow further only
+/// as elements are actually read, so that a forged length prefix cannot exhaust memory)
+const MAX_PREALLOCATION: usize = 64 * 1024;
+
+/// Returns the number of elements to pre-allocate for a sequence of `len` elements decoded from a reader
+#[inline]
+pub(crate) fn reader_capacity<T>(len: usize) -> usize {
+    cmp::min(len, MAX_PREALLOCATION / cmp::max(mem::size_of::<T>(), 1))
+}
+
+/// Returns the number of elements to pre-allocate for a sequence of `len` elements decoded from `bytes` (every element
+/// except zero-sized ones takes at least one byte)
+#[inline]
+pub(crate) fn slice_capacity(len: usize, bytes: &[u8]) -> usize {
+    cmp::min(len, bytes.len())
+}
+
+/// Reads `len` bytes from reader and appends them to `buf` (growing `buf` only as bytes are read)
+pub(crate) async fn read_bytes_into<R>(buf: &mut Vec<u8>, len: usize, mut reader: R) -> Result<()>
+where
+    R: Read + Unpin,
+{
+    // A source can never contain more than `usize::MAX` bytes
+    let end = buf.len().checked_add(len).ok_or(Error::UnexpectedEof)?;
+
+    while buf.len() < end {
+        let start = buf.len();
+        let chunk_len = cmp::min(end - start, cmp::max(start, MAX_PREALLOCATION));
+
+        buf.resize(start + chunk_len, 0);
+        reader.read_exact(&mut buf[start..]).await?;
+    }
+
+    Ok(())
+}
+
+/// Reads `len` primitive values encoded with `config.endianness` from reader and appends them to `values` (growing
+/// `values` only as values are read)
+pub(crate) async fn read_values_into<T, R>(
+    values: &mut Vec<T>,
+    len: usize,
+    config: &Config,
+    mut reader: R,
+) -> Result<()>
+where
+    T: bytemuck::Pod + Send,
+    R: Read + Unpin,
+{
+    // A source can never contain more than `usize::MAX` bytes
+    len.checked_mul(mem::size_of::<T>())
+        .ok_or(Error::UnexpectedEof)?;
+
+    let first = values.len();
+    let end = first.checked_add(len).ok_or(Error::UnexpectedEof)?;
+
+    while values.len() < end {
+        let start = values.len();
+        let chunk_len = cmp::min(
+            end - start,
+            cmp::max(start, reader_capacity::<T>(end - start)),
+        );
+
+        values.resize(start + chunk_len, T::zeroed());
+        reader
+            .read_exact(bytemuck::cast_slice_mut(&mut values[start..]))
+            .await?;
+    }
+
+    swap_values(&mut values[first..], config);
+
+    Ok(())
+}
+
+/// Converts primitive values whose bytes are encoded with `config.endianness` to native endianness in place
+pub(crate) fn swap_values<T: bytemuck::Pod>(values: &mut [T], config: &Config) {
+    if !config.endianness.is_native() {
+        bytemuck::cast_slice_mut::<T, u8>(values)
+            .chunks_exact_mut(mem::size_of::<T>())
+            .for_each(<[u8]>::reverse);
+    }
+}
+
+/// Selects how `decode_in_place()` of a derived struct decodes a field of type `T` (used by code generated from derive
+/// macros)
+///
+/// Calling `(&FieldProbe::of(&field)).decode_field(..)` resolves to [`DecodeFieldInPlace`] when `T` is `Send` and
+/// to [`DecodeFieldReplace`] otherwise, so that derived structs do not require their fields to be `Send`.
+#[doc(hidden)]
+pub struct FieldProbe<T>(PhantomData<fn() -> T>);
+
+impl<T> FieldProbe<T> {
+    #[inline]
+    pub fn of(_field: &T) -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// Decodes a field in place with `Decode::decode_in_place()` (returns `None`)
+#[doc(hidden)]
+pub trait DecodeFieldInPlace<T> {
+    fn decode_field<'a, R>(
+        &self,
+        field: &'a mut T,
+        config: &'a Config,
+        reader: R,
+    ) -> impl Future<Output = Result<Option<T>>> + Send + 'a
+    where
+        R: Read + Unpin + Send + 'a;
+}
+
+impl<T: Decode + Send> DecodeFieldInPlace<T> for FieldProbe<T> {
+    // Returned future must not capture the lifetime of `self`
+    #[allow(clippy::manual_async_fn)]
+    #[inline]
+    fn decode_field<'a, R>(
+        &self,
+        field: &'a mut T,
+        config: &'a Config,
+        reader: R,
+    ) -> impl Future<Output = Result<Option<T>>> + Send + 'a
+    where
+        R: Read + Unpin + Send + 'a,
+    {
+        async move {
+            field.decode_in_place(config, reader).await?;
+            Ok(None)
+        }
+    }
+}
+
+/// Decodes a new value of a field with `Decode::decode_from()` (returns `Some` value which replaces the field)
+#[doc(hidden)]
+pub trait DecodeFieldReplace<T> {
+    fn decode_field<'a, R>(
+        &self,
+        field: &'a mut T,
+        config: &'a Config,
+        reader: R,
+    ) -> impl Future<Output = Result<Option<T>>> + Send + 'a
+    where
+        R: Read + Unpin + Send + 'a;
+}
+
+impl<T: Decode> DecodeFieldReplace<T> for &FieldProbe<T> {
+    // Returned future must not capture the lifetime of `self`
+    #[allow(clippy::manual_async_fn)]
+    #[inline]
+    fn decode_field<'a, R>(
+        &self,
+        _field: &'a mut T,
+        config: &'a Config,
+        reader: R,
+    ) -> impl Future<Output = Result<Option<T>>> + Send + 'a
+    where
+        R: Read + Unpin + Send + 'a,
+    {
+        async move { T::decode_from(config, reader).await.map(Some) }
+    }
 }
 
 macro_rules! impl_primitive {
     ($($type: ty),+) => {
         $(
-            #[async_trait]
             impl Decode for $type {
                 async fn decode_from<R>(config: &Config, mut reader: R) -> Result<Self>
                 where
@@ -61,14 +372,113 @@ macro_rules! impl_primitive {
                         }
                     }
                 }
+
+                #[inline]
+                fn decode_from_slice(config: &Config, bytes: &mut &[u8]) -> Result<Self> {
+                    let mut buf = [0u8; core::mem::size_of::<$type>()];
+                    buf.copy_from_slice(take_bytes(bytes, core::mem::size_of::<$type>())?);
+
+                    match config.endianness {
+                        Endianness::LittleEndian => {
+                            Ok(<$type>::from_le_bytes(buf))
+                        }
+                        Endianness::BigEndian => {
+                            Ok(<$type>::from_be_bytes(buf))
+                        }
+                    }
+                }
+
+                async fn decode_vec_from<R>(len: usize, config: &Config, reader: R) -> Result<Vec<Self>>
+                where
+                    R: Read + Unpin + Send,
+                {
+                    let mut values = Vec::new();
+                    read_values_into(&mut values, len, config, reader).await?;
+                    Ok(values)
+                }
+
+                fn decode_vec_from_slice(len: usize, config: &Config, bytes: &mut &[u8]) -> Result<Vec<Self>> {
+                    let bytes_len = len
+                        .checked_mul(core::mem::size_of::<$type>())
+                        .ok_or(Error::UnexpectedEof)?;
+                    let src = take_bytes(bytes, bytes_len)?;
+
+                    let mut values = vec![<$type>::default(); len];
+                    bytemuck::cast_slice_mut::<$type, u8>(&mut values).copy_from_slice(src);
+                    swap_values(&mut values, config);
+
+                    Ok(values)
+                }
+
+                #[cfg(feature = "bytes")]
+                fn decode_vec_from_bytes(len: usize, config: &Config, bytes: &mut Bytes) -> Result<Vec<Self>> {
+                    decode_from_bytes_with(bytes, |slice| Self::decode_vec_from_slice(len, config, slice))
+                }
+
+                async fn decode_vec_in_place<R>(vec: &mut Vec<Self>, len: usize, config: &Config, reader: R) -> Result<()>
+                where
+                    R: Read + Unpin + Send,
+                {
+                    vec.clear();
+                    read_values_into(vec, len, config, reader).await
+                }
             }
         )+
     };
 }
 
-impl_primitive!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, usize, isize, f32, f64);
+impl_primitive!(u16, u32, u64, u128, i8, i16, i32, i64, i128, usize, isize, f32, f64);
+
+impl Decode for u8 {
+    async fn decode_from<R>(_config: &Config, mut reader: R) -> Result<Self>
+    where
+        R: Read + Unpin + Send,
+    {
+        let mut byte = [0u8];
+        reader.read_exact(&mut byte).await?;
+        Ok(byte[0])
+    }
+
+    #[inline]
+    fn decode_from_slice(_config: &Config, bytes: &mut &[u8]) -> Result<Self> {
+        Ok(take_bytes(bytes, 1)?[0])
+    }
+
+    async fn decode_vec_from<R>(len: usize, _config: &Config, mut reader: R) -> Result<Vec<Self>>
+    where
+        R: Read + Unpin + Send,
+    {
+        let mut values = Vec::new();
+        read_bytes_into(&mut values, len, &mut reader).await?;
+        Ok(values)
+    }
+
+    #[inline]
+    fn decode_vec_from_slice(len: usize, _config: &Config, bytes: &mut &[u8]) -> Result<Vec<Self>> {
+        take_bytes(bytes, len).map(<[u8]>::to_vec)
+    }
+
+    #[cfg(feature = "bytes")]
+    fn decode_vec_from_bytes(len: usize, config: &Config, bytes: &mut Bytes) -> Result<Vec<Self>> {
+        decode_from_bytes_with(bytes, |slice| {
+            Self::decode_vec_from_slice(len, config, slice)
+        })
+    }
+
+    async fn decode_vec_in_place<R>(
+        vec: &mut Vec<Self>,
+        len: usize,
+        _config: &Config,
+        reader: R,
+    ) -> Result<()>
+    where
+        R: Read + Unpin + Send,
+    {
+        vec.clear();
+        read_bytes_into(vec, len, reader).await
+    }
+}
 
-#[async_trait]
 impl Decode for bool {
     async fn decode_from<R>(config: &Config, reader: R) -> Result<Self>
     where
@@ -76,9 +486,13 @@ impl Decode for bool {
     {
         Ok(<u8>::decode_from(config, reader).await? != 0)
     }
+
+    #[inline]
+    fn decode_from_slice(config: &Config, bytes: &mut &[u8]) -> Result<Self> {
+        Ok(<u8>::decode_from_slice(config, bytes)? != 0)
+    }
 }
 
-#[async_trait]
 impl Decode for char {
     async fn decode_from<R>(config: &Config, reader: R) -> Result<Self>
     where
@@ -87,9 +501,13 @@ impl Decode for char {
         let code = <u32>::decode_from(config, reader).await?;
         core::char::from_u32(code).ok_or(Error::InvalidChar(code))
     }
+
+    fn decode_from_slice(config: &Config, bytes: &mut &[u8]) -> Result<Self> {
+        let code = <u32>::decode_from_slice(config, bytes)?;
+        core::char::from_u32(code).ok_or(Error::InvalidChar(code))
+    }
 }
 
-#[async_trait]
 impl<T> Decode for Option<T>
 where
     T: Decode,
@@ -106,9 +524,29 @@ where
             _ => Err(Error::InvalidEnumVariant(option.into())),
         }
     }
+
+    fn decode_from_slice(config: &Config, bytes: &mut &[u8]) -> Result<Self> {
+        let option = u8::decode_from_slice(config, bytes)?;
+
+        match option {
+            0 => Ok(None),
+            1 => T::decode_from_slice(config, bytes).map(Some),
+            _ => Err(Error::InvalidEnumVariant(option.into())),
+        }
+    }
+
+    #[cfg(feature = "bytes")]
+    fn decode_from_bytes(config: &Config, bytes: &mut Bytes) -> Result<Self> {
+        let option = u8::decode_from_bytes(config, bytes)?;
+
+        match option {
+            0 => Ok(None),
+            1 => T::decode_from_bytes(config, bytes).map(Some),
+            _ => Err(Error::InvalidEnumVariant(option.into())),
+        }
+    }
 }
 
-#[async_trait]
 impl<T, E> Decode for core::result::Result<T, E>
 where
     T: Decode,
@@ -126,6 +564,27 @@ where
             _ => Err(Error::InvalidEnumVariant(option.into())),
         }
     }
+
+    fn decode_from_slice(config: &Config, bytes: &mut &[u8]) -> Result<Self> {
+        let option = u8::decode_from_slice(config, bytes)?;
+
+        match option {
+            0 => T::decode_from_slice(config, bytes).map(Ok),
+            1 => E::decode_from_slice(config, bytes).map(Err),
+            _ => Err(Error::InvalidEnumVariant(option.into())),
+        }
+    }
+
+    #[cfg(feature = "bytes")]
+    fn decode_from_bytes(config: &Config, bytes: &mut Bytes) -> Result<Self> {
+        let option = u8::decode_from_bytes(config, bytes)?;
+
+        match option {
+            0 => T::decode_from_bytes(config, bytes).map(Ok),
+            1 => E::decode_from_bytes(config, bytes).map(Err),
+            _ => Err(Error::InvalidEnumVariant(option.into())),
+        }
+    }
 }
 
 macro_rules! impl_seq {
@@ -135,7 +594,6 @@ macro_rules! impl_seq {
         $create: expr,
         $insert: expr
     ) => {
-        #[async_trait]
         impl<T $(, $typaram)*> Decode for $ty<T $(, $typaram)*>
         where
             T: Decode + Send $(+ $tbound1 $(+ $tbound2)*)*,
@@ -145,21 +603,89 @@ macro_rules! impl_seq {
             where
                 R: Read + Unpin + Send,
             {
-                let $len = usize::try_from(VarInt::decode_from(config, &mut reader).await?)?;
+                let len = usize::try_from(VarInt::decode_from(config, &mut reader).await?)?;
 
-                let mut value = $create;
+                let mut value = {
+                    #[allow(unused_variables)]
+                    let $len = reader_capacity::<T>(len);
+                    $create
+                };
 
-                for _ in 0..$len {
+                for _ in 0..len {
                     $insert(&mut value, T::decode_from(config, &mut reader).await?);
                 }
 
                 Ok(value)
             }
+
+            fn decode_from_slice(config: &Config, bytes: &mut &[u8]) -> Result<Self> {
+                let len = usize::try_from(VarInt::decode_from_slice(config, bytes)?)?;
+
+                let mut value = {
+                    #[allow(unused_variables)]
+                    let $len = slice_capacity(len, bytes);
+                    $create
+                };
+
+                for _ in 0..len {
+                    $insert(&mut value, T::decode_from_slice(config, bytes)?);
+                }
+
+                Ok(value)
+            }
+
+            #[cfg(feature = "bytes")]
+            fn decode_from_bytes(config: &Config, bytes: &mut Bytes) -> Result<Self> {
+                let len = usize::try_from(VarInt::decode_from_bytes(config, bytes)?)?;
+
+                let mut value = {
+                    #[allow(unused_variables)]
+                    let $len = slice_capacity(len, bytes);
+                    $create
+                };
+
+                for _ in 0..len {
+                    $insert(&mut value, T::decode_from_bytes(config, bytes)?);
+                }
+
+                Ok(value)
+            }
         }
     };
 }
 
-impl_seq!(Vec<T>, len, Vec::with_capacity(len), Vec::push);
+impl<T> Decode for Vec<T>
+where
+    T: Decode + Send,
+{
+    async fn decode_from<R>(config: &Config, mut reader: R) -> Result<Self>
+    where
+        R: Read + Unpin + Send,
+    {
+        let len = usize::try_from(VarInt::decode_from(config, &mut reader).await?)?;
+        T::decode_vec_from(len, config, &mut reader).await
+    }
+
+    async fn decode_in_place<R>(&mut self, config: &Config, mut reader: R) -> Result<()>
+    where
+        R: Read + Unpin + Send,
+    {
+        let len = usize::try_from(VarInt::decode_from(config, &mut reader).await?)?;
+        T::decode_vec_in_place(self, len, config, &mut reader).await
+    }
+
+    fn decode_from_slice(config: &Config, bytes: &mut &[u8]) -> Result<Self> {
+        let len = usize::try_from(VarInt::decode_from_slice(config, bytes)?)?;
+        T::decode_vec_from_slice(len, config, bytes)
+    }
+
+    #[cfg(feature = "bytes")]
+    fn decode_from_bytes(config: &Config, bytes: &mut Bytes) -> Result<Self> {
+        let len = usize::try_from(VarInt::decode_from_bytes(config, bytes)?)?;
+        T::decode_vec_from_bytes(len, config, bytes)
+    }
+}
+
 impl_seq!(
     VecDeque<T>,
     len,
@@ -167,6 +693,7 @@ impl_seq!(
     VecDeque::push_back
 );
 impl_seq!(LinkedList<T>, len, LinkedList::new(), LinkedList::push_back);
+#[cfg(feature = "std")]
 impl_seq!(
     HashSet<T: Eq + Hash, S: BuildHasher + Default + Send>,
     len,
@@ -178,7 +705,6 @@ impl_seq!(BinaryHeap<T: Ord>, len, BinaryHeap::new(), BinaryHeap::push);
 
 macro_rules! impl_from_bytes {
     ($type: ty, $create: ident) => {
-        #[async_trait]
         impl Decode for $type {
             async fn decode_from<R>(config: &Config, reader: R) -> Result<Self>
             where
@@ -187,25 +713,68 @@ macro_rules! impl_from_bytes {
                 let bytes = <Vec<u8>>::decode_from(config, reader).await?;
                 Self::$create(bytes).map_err(Into::into)
             }
+
+            fn decode_from_slice(config: &Config, bytes: &mut &[u8]) -> Result<Self> {
+                let bytes = <Vec<u8>>::decode_from_slice(config, bytes)?;
+                Self::$create(bytes).map_err(Into::into)
+            }
         }
     };
 }
 
-impl_from_bytes!(String, from_utf8);
 impl_from_bytes!(CString, new);
 
+impl Decode for String {
+    async fn decode_from<R>(config: &Config, reader: R) -> Result<Self>
+    where
+        R: Read + Unpin + Send,
+    {
+        let bytes = <Vec<u8>>::decode_from(config, reader).await?;
+        Self::from_utf8(bytes).map_err(Into::into)
+    }
+
+    fn decode_from_slice(config: &Config, bytes: &mut &[u8]) -> Result<Self> {
+        let bytes = <Vec<u8>>::decode_from_slice(config, bytes)?;
+        Self::from_utf8(bytes).map_err(Into::into)
+    }
+
+    async fn decode_in_place<R>(&mut self, config: &Config, reader: R) -> Result<()>
+    where
+        R: Read + Unpin + Send,
+    {
+        let mut bytes = core::mem::take(self).into_bytes();
+        bytes.decode_in_place(config, reader).await?;
+        *self = Self::from_utf8(bytes)?;
+        Ok(())
+    }
+}
+
+/// Smart pointers are used as indirection in recursive types. So, future of inner value is boxed (to keep size of the
+/// future finite) and reader is passed as a trait object (to keep number of monomorphized functions finite).
 macro_rules! impl_deref {
     ($type: ty, $func: expr) => {
-        #[async_trait]
         impl<T> Decode for $type
         where
             T: Decode,
         {
-            async fn decode_from<R>(config: &Config, reader: R) -> Result<Self>
+            async fn decode_from<R>(config: &Config, mut reader: R) -> Result<Self>
             where
                 R: Read + Unpin + Send,
             {
-                T::decode_from(config, reader).await.map($func)
+                let reader: &mut DynRead<'_> = &mut reader;
+                let future: BoxFuture<'_, Result<T>> = Box::pin(T::decode_from(config, reader));
+                future.await.map($func)
+            }
+
+            #[inline]
+            fn decode_from_slice(config: &Config, bytes: &mut &[u8]) -> Result<Self> {
+                T::decode_from_slice(config, bytes).map($func)
+            }
+
+            #[cfg(feature = "bytes")]
+            #[inline]
+            fn decode_from_bytes(config: &Config, bytes: &mut Bytes) -> Result<Self> {
+                T::decode_from_bytes(config, bytes).map($func)
             }
         }
     };
@@ -215,7 +784,29 @@ impl_deref!(Box<T>, Box::new);
 impl_deref!(Rc<T>, Rc::new);
 impl_deref!(Arc<T>, Arc::new);
 
-#[async_trait]
+impl<T> Decode for RefCell<T>
+where
+    T: Decode,
+{
+    async fn decode_from<R>(config: &Config, reader: R) -> Result<Self>
+    where
+        R: Read + Unpin + Send,
+    {
+        T::decode_from(config, reader).await.map(RefCell::new)
+    }
+
+    #[inline]
+    fn decode_from_slice(config: &Config, bytes: &mut &[u8]) -> Result<Self> {
+        T::decode_from_slice(config, bytes).map(RefCell::new)
+    }
+
+    #[cfg(feature = "bytes")]
+    #[inline]
+    fn decode_from_bytes(config: &Config, bytes: &mut Bytes) -> Result<Self> {
+        T::decode_from_bytes(config, bytes).map(RefCell::new)
+    }
+}
+
 impl<'a, T: ?Sized> Decode for Cow<'a, T>
 where
     T: 'a + ToOwned,
@@ -228,6 +819,64 @@ where
         let owned = <<T as ToOwned>::Owned>::decode_from(config, reader).await?;
         Ok(Cow::Owned(owned))
     }
+
+    fn decode_from_slice(config: &Config, bytes: &mut &[u8]) -> Result<Self> {
+        let owned = <<T as ToOwned>::Owned>::decode_from_slice(config, bytes)?;
+        Ok(Cow::Owned(owned))
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl Decode for Bytes {
+    async fn decode_from<R>(config: &Config, reader: R) -> Result<Self>
+    where
+        R: Read + Unpin + Send,
+    {
+        <Vec<u8>>::decode_from(config, reader).await.map(Into::into)
+    }
+
+    fn decode_from_slice(config: &Config, bytes: &mut &[u8]) -> Result<Self> {
+        let len = usize::try_from(VarInt::decode_from_slice(config, bytes)?)?;
+        take_bytes(bytes, len).map(Bytes::copy_from_slice)
+    }
+
+    fn decode_from_bytes(config: &Config, bytes: &mut Bytes) -> Result<Self> {
+        let len = usize::try_from(VarInt::decode_from_bytes(config, bytes)?)?;
+
+        if bytes.len() < len {
+            return Err(Error::UnexpectedEof);
+        }
+
+        Ok(bytes.split_to(len))
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl Decode for BytesMut {
+    async fn decode_from<R>(config: &Config, mut reader: R) -> Result<Self>
+    where
+        R: Read + Unpin + Send,
+    {
+        let len = usize::try_from(VarInt::decode_from(config, &mut reader).await?)?;
+
+        let mut value = BytesMut::new();
+
+        // Buffer grows only as bytes are read (like `read_bytes_into()`)
+        while value.len() < len {
+            let start = value.len();
+            let chunk_len = cmp::min(len - start, cmp::max(start, MAX_PREALLOCATION));
+
+            value.resize(start + chunk_len, 0);
+            reader.read_exact(&mut value[start..]).await?;
+        }
+
+        Ok(value)
+    }
+
+    fn decode_from_slice(config: &Config, bytes: &mut &[u8]) -> Result<Self> {
+        let len = usize::try_from(VarInt::decode_from_slice(config, bytes)?)?;
+        take_bytes(bytes, len).map(BytesMut::from)
+    }
 }
 
 macro_rules! impl_map {
@@ -236,7 +885,6 @@ macro_rules! impl_map {
         $len: ident,
         $create: expr
     ) => {
-        #[async_trait]
         impl<K, V $(, $typaram)*> Decode for $ty<K, V $(, $typaram)*>
         where
             K: Decode + Send $(+ $kbound1 $(+ $kbound2)*)*,
@@ -244,15 +892,70 @@ macro_rules! impl_map {
             $($typaram: $bound1 $(+ $bound2)*,)*
         {
             async fn decode_from<R>(config: &Config, mut reader: R) -> Result<Self>
+            where
+                R: Read + Unpin + Send,
+            {
+                let len = usize::try_from(VarInt::decode_from(config, &mut reader).await?)?;
+
+                let mut map = {
+                    #[allow(unused_variables)]
+                    let $len = reader_capacity::<(K, V)>(len);
+                    $create
+                };
+
+                for _ in 0..len {
+                    let entry = <(K, V)>::decode_from(config, &mut reader).await?;
+                    map.insert(entry.0, entry.1);
+                }
+
+                Ok(map)
+            }
+
+            async fn decode_in_place<R>(&mut self, config: &Config, mut reader: R) -> Result<()>
             where
                 R: Read + Unpin + Send,
             {
                 let $len = usize::try_from(VarInt::decode_from(config, &mut reader).await?)?;
 
-                let mut map = $create;
+                self.clear();
 
                 for _ in 0..$len {
                     let entry = <(K, V)>::decode_from(config, &mut reader).await?;
+                    self.insert(entry.0, entry.1);
+                }
+
+                Ok(())
+            }
+
+            fn decode_from_slice(config: &Config, bytes: &mut &[u8]) -> Result<Self> {
+                let len = usize::try_from(VarInt::decode_from_slice(config, bytes)?)?;
+
+                let mut map = {
+                    #[allow(unused_variables)]
+                    let $len = slice_capacity(len, bytes);
+                    $create
+                };
+
+                for _ in 0..len {
+                    let entry = <(K, V)>::decode_from_slice(config, bytes)?;
+                    map.insert(entry.0, entry.1);
+                }
+
+                Ok(map)
+            }
+
+            #[cfg(feature = "bytes")]
+            fn decode_from_bytes(config: &Config, bytes: &mut Bytes) -> Result<Self> {
+                let len = usize::try_from(VarInt::decode_from_bytes(config, bytes)?)?;
+
+                let mut map = {
+                    #[allow(unused_variables)]
+                    let $len = slice_capacity(len, bytes);
+                    $create
+                };
+
+                for _ in 0..len {
+                    let entry = <(K, V)>::decode_from_bytes(config, bytes)?;
                     map.insert(entry.0, entry.1);
                 }
 
@@ -262,6 +965,7 @@ macro_rules! impl_map {
     };
 }
 
+#[cfg(feature = "std")]
 impl_map!(
     HashMap<K: Eq + Hash, V, S: BuildHasher + Default + Send>,
     len,
@@ -272,23 +976,27 @@ impl_map!(BTreeMap<K: Ord, V>, len, BTreeMap::new());
 macro_rules! impl_fixed_arr {
     ($($len: expr),+) => {
         $(
-            #[async_trait]
             impl<T> Decode for [T; $len]
             where
                 T: Decode + Send,
             {
-                async fn decode_from<R>(config: &Config, mut reader: R) -> Result<Self>
+                async fn decode_from<R>(config: &Config, reader: R) -> Result<Self>
                 where
                     R: Read + Unpin + Send,
                 {
-                    let mut arr = ArrayVec::<[T; $len]>::new();
+                    let values = T::decode_vec_from($len, config, reader).await?;
+                    Self::try_from(values).map_err(|_| Error::PartiallyFilledArray)
+                }
 
-                    for _ in 0..$len {
-                        let value = T::decode_from(config, &mut reader).await?;
-                        arr.push(value)
-                    }
+                fn decode_from_slice(config: &Config, bytes: &mut &[u8]) -> Result<Self> {
+                    let values = T::decode_vec_from_slice($len, config, bytes)?;
+                    Self::try_from(values).map_err(|_| Error::PartiallyFilledArray)
+                }
 
-                    arr.into_inner().map_err(|_| Error::PartiallyFilledArray)
+                #[cfg(feature = "bytes")]
+                fn decode_from_bytes(config: &Config, bytes: &mut Bytes) -> Result<Self> {
+                    let values = T::decode_vec_from_bytes($len, config, bytes)?;
+                    Self::try_from(values).map_err(|_| Error::PartiallyFilledArray)
                 }
             }
         )+
@@ -301,7 +1009,6 @@ impl_fixed_arr!(
     51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63, 64, 128, 256, 512, 1024
 );
 
-#[async_trait]
 impl Decode for () {
     async fn decode_from<R>(_config: &Config, _reader: R) -> Result<Self>
     where
@@ -309,12 +1016,16 @@ impl Decode for () {
     {
         Ok(())
     }
+
+    #[inline]
+    fn decode_from_slice(_config: &Config, _bytes: &mut &[u8]) -> Result<Self> {
+        Ok(())
+    }
 }
 
 macro_rules! impl_tuple {
     ($(($($name:tt)+))+) => {
         $(
-            #[async_trait]
             impl<$($name),+> Decode for ($($name,)+)
             where
                 $($name: Decode + Send,)+
@@ -329,6 +1040,25 @@ macro_rules! impl_tuple {
                         )+
                     ))
                 }
+
+                #[inline]
+                fn decode_from_slice(config: &Config, bytes: &mut &[u8]) -> Result<Self> {
+                    Ok((
+                        $(
+                            $name::decode_from_slice(config, bytes)?,
+                        )+
+                    ))
+                }
+
+                #[cfg(feature = "bytes")]
+                #[inline]
+                fn decode_from_bytes(config: &Config, bytes: &mut Bytes) -> Result<Self> {
+                    Ok((
+                        $(
+                            $name::decode_from_bytes(config, bytes)?,
+                        )+
+                    ))
+                }
             }
         )+
     }
@@ -353,7 +1083,6 @@ impl_tuple! {
     (T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13 T14 T15)
 }
 
-#[async_trait]
 impl<T> Decode for PhantomData<T>
 where
     T: ?Sized,
@@ -364,19 +1093,28 @@ where
     {
         Ok(Default::default())
     }
+
+    #[inline]
+    fn decode_from_slice(_config: &Config, _bytes: &mut &[u8]) -> Result<Self> {
+        Ok(Default::default())
+    }
 }
 
 macro_rules! impl_non_zero_primitives {
-    ($($type: ident),+) => {
+    ($($type: ident => $primitive: ty),+) => {
         $(
-            #[async_trait]
             impl Decode for $type {
                 async fn decode_from<R>(config: &Config, reader: R) -> Result<Self>
                 where
                     R: Read + Unpin + Send,
                 {
-                    Ok(Self::new(Decode::decode_from(config, reader).await?)
-                        .ok_or_else(|| Error::NonZeroError)?)
+                    Self::new(<$primitive>::decode_from(config, reader).await?)
+                        .ok_or(Error::NonZeroError)
+                }
+
+                #[inline]
+                fn decode_from_slice(config: &Config, bytes: &mut &[u8]) -> Result<Self> {
+                    Self::new(<$primitive>::decode_from_slice(config, bytes)?).ok_or(Error::NonZeroError)
                 }
             }
         )+
@@ -384,16 +1122,16 @@ macro_rules! impl_non_zero_primitives {
 }
 
 impl_non_zero_primitives!(
-    NonZeroU8,
-    NonZeroU16,
-    NonZeroU32,
-    NonZeroU64,
-    NonZeroU128,
-    NonZeroI8,
-    NonZeroI16,
-    NonZeroI32,
-    NonZeroI64,
-    NonZeroI128,
-    NonZeroUsize,
-    NonZeroIsize
+    NonZeroU8 => u8,
+    NonZeroU16 => u16,
+    NonZeroU32 => u32,
+    NonZeroU64 => u64,
+    NonZeroU128 => u128,
+    NonZeroI8 => i8,
+    NonZeroI16 => i16,
+    NonZeroI32 => i32,
+    NonZeroI64 => i64,
+    NonZeroI128 => i128,
+    NonZeroUsize => usize,
+    NonZeroIsize => isize
 );
@@ -0,0 +1,313 @@
+use alloc::{
+    borrow::Cow,
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet, BinaryHeap, LinkedList, VecDeque},
+    ffi::CString,
+    rc::Rc,
+    string::String,
+    sync::Arc,
+    vec::Vec,
+};
+#[cfg(feature = "std")]
+use core::hash::{BuildHasher, Hash};
+use core::{
+    cell::RefCell,
+    convert::TryFrom,
+    marker::PhantomData,
+    num::{
+        NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+        NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+    },
+};
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
+
+use crate::{decode::slice_capacity, io::take_bytes, Config, Decode, Error, Result, VarInt};
+
+/// Trait for decoding values which can borrow from input byte slice
+///
+/// Unlike [`Decode`](trait.Decode.html), decoded values can borrow data directly from the input (for example, `&str`,
+/// `&[u8]` and `Cow::Borrowed`) without copying it. Values of types implementing `Decode` are decoded using
+/// `Decode::decode_from_slice()`.
+pub trait DecodeBorrowed<'de>: Sized {
+    /// Decodes value from the start of `bytes` and advances `bytes` past the decoded value
+    fn decode_borrowed(config: &Config, bytes: &mut &'de [u8]) -> Result<Self>;
+}
+
+macro_rules! impl_owned {
+    ($($type: ty),+) => {
+        $(
+            impl<'de> DecodeBorrowed<'de> for $type {
+                #[inline]
+                fn decode_borrowed(config: &Config, bytes: &mut &'de [u8]) -> Result<Self> {
+                    <$type>::decode_from_slice(config, bytes)
+                }
+            }
+        )+
+    };
+}
+
+impl_owned!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, usize, isize, f32, f64);
+impl_owned!(bool, char, String, CString, (), VarInt);
+impl_owned!(
+    NonZeroU8,
+    NonZeroU16,
+    NonZeroU32,
+    NonZeroU64,
+    NonZeroU128,
+    NonZeroUsize
+);
+impl_owned!(
+    NonZeroI8,
+    NonZeroI16,
+    NonZeroI32,
+    NonZeroI64,
+    NonZeroI128,
+    NonZeroIsize
+);
+#[cfg(feature = "bytes")]
+impl_owned!(bytes::Bytes, bytes::BytesMut);
+
+impl<'de: 'a, 'a> DecodeBorrowed<'de> for &'a [u8] {
+    fn decode_borrowed(config: &Config, bytes: &mut &'de [u8]) -> Result<Self> {
+        let len = usize::try_from(VarInt::decode_from_slice(config, bytes)?)?;
+        take_bytes(bytes, len)
+    }
+}
+
+impl<'de: 'a, 'a> DecodeBorrowed<'de> for &'a str {
+    fn decode_borrowed(config: &Config, bytes: &mut &'de [u8]) -> Result<Self> {
+        let bytes = <&[u8]>::decode_borrowed(config, bytes)?;
+        core::str::from_utf8(bytes).map_err(Into::into)
+    }
+}
+
+macro_rules! impl_borrowed_cow {
+    ($($type: ty),+) => {
+        $(
+            impl<'de: 'a, 'a> DecodeBorrowed<'de> for Cow<'a, $type> {
+                #[inline]
+                fn decode_borrowed(config: &Config, bytes: &mut &'de [u8]) -> Result<Self> {
+                    <&$type>::decode_borrowed(config, bytes).map(Cow::Borrowed)
+                }
+            }
+        )+
+    };
+}
+
+impl_borrowed_cow!(str, [u8]);
+
+impl<'de, T> DecodeBorrowed<'de> for Option<T>
+where
+    T: DecodeBorrowed<'de>,
+{
+    fn decode_borrowed(config: &Config, bytes: &mut &'de [u8]) -> Result<Self> {
+        let option = u8::decode_from_slice(config, bytes)?;
+
+        match option {
+            0 => Ok(None),
+            1 => T::decode_borrowed(config, bytes).map(Some),
+            _ => Err(Error::InvalidEnumVariant(option.into())),
+        }
+    }
+}
+
+impl<'de, T, E> DecodeBorrowed<'de> for core::result::Result<T, E>
+where
+    T: DecodeBorrowed<'de>,
+    E: DecodeBorrowed<'de>,
+{
+    fn decode_borrowed(config: &Config, bytes: &mut &'de [u8]) -> Result<Self> {
+        let option = u8::decode_from_slice(config, bytes)?;
+
+        match option {
+            0 => T::decode_borrowed(config, bytes).map(Ok),
+            1 => E::decode_borrowed(config, bytes).map(Err),
+            _ => Err(Error::InvalidEnumVariant(option.into())),
+        }
+    }
+}
+
+macro_rules! impl_seq {
+    (
+        $ty: ident < T $(: $tbound1: ident $(+ $tbound2: ident)*)* $(, $typaram: ident : $bound1: ident $(+ $bound2: ident)*)* >,
+        $len: ident,
+        $create: expr,
+        $insert: expr
+    ) => {
+        impl<'de, T $(, $typaram)*> DecodeBorrowed<'de> for $ty<T $(, $typaram)*>
+        where
+            T: DecodeBorrowed<'de> $(+ $tbound1 $(+ $tbound2)*)*,
+            $($typaram: $bound1 $(+ $bound2)*,)*
+        {
+            fn decode_borrowed(config: &Config, bytes: &mut &'de [u8]) -> Result<Self> {
+                let len = usize::try_from(VarInt::decode_from_slice(config, bytes)?)?;
+
+                let mut value = {
+                    #[allow(unused_variables)]
+                    let $len = slice_capacity(len, bytes);
+                    $create
+                };
+
+                for _ in 0..len {
+                    $insert(&mut value, T::decode_borrowed(config, bytes)?);
+                }
+
+                Ok(value)
+            }
+        }
+    };
+}
+
+impl_seq!(Vec<T>, len, Vec::with_capacity(len), Vec::push);
+impl_seq!(
+    VecDeque<T>,
+    len,
+    VecDeque::with_capacity(len),
+    VecDeque::push_back
+);
+impl_seq!(LinkedList<T>, len, LinkedList::new(), LinkedList::push_back);
+#[cfg(feature = "std")]
+impl_seq!(
+    HashSet<T: Eq + Hash, S: BuildHasher + Default>,
+    len,
+    HashSet::with_capacity_and_hasher(len, S::default()),
+    HashSet::insert
+);
+impl_seq!(BTreeSet<T: Ord>, len, BTreeSet::new(), BTreeSet::insert);
+impl_seq!(BinaryHeap<T: Ord>, len, BinaryHeap::new(), BinaryHeap::push);
+
+macro_rules! impl_deref {
+    ($type: ty, $func: expr) => {
+        impl<'de, T> DecodeBorrowed<'de> for $type
+        where
+            T: DecodeBorrowed<'de>,
+        {
+            #[inline]
+            fn decode_borrowed(config: &Config, bytes: &mut &'de [u8]) -> Result<Self> {
+                T::decode_borrowed(config, bytes).map($func)
+            }
+        }
+    };
+}
+
+impl_deref!(Box<T>, Box::new);
+impl_deref!(Rc<T>, Rc::new);
+impl_deref!(Arc<T>, Arc::new);
+impl_deref!(RefCell<T>, RefCell::new);
+
+macro_rules! impl_map {
+    (
+        $ty: ident < K $(: $kbound1: ident $(+ $kbound2: ident)*)*, V $(, $typaram: ident : $bound1: ident $(+ $bound2: ident)*)* >,
+        $len: ident,
+        $create: expr
+    ) => {
+        impl<'de, K, V $(, $typaram)*> DecodeBorrowed<'de> for $ty<K, V $(, $typaram)*>
+        where
+            K: DecodeBorrowed<'de> $(+ $kbound1 $(+ $kbound2)*)*,
+            V: DecodeBorrowed<'de>,
+            $($typaram: $bound1 $(+ $bound2)*,)*
+        {
+            fn decode_borrowed(config: &Config, bytes: &mut &'de [u8]) -> Result<Self> {
+                let len = usize::try_from(VarInt::decode_from_slice(config, bytes)?)?;
+
+                let mut map = {
+                    #[allow(unused_variables)]
+                    let $len = slice_capacity(len, bytes);
+                    $create
+                };
+
+                for _ in 0..len {
+                    let entry = <(K, V)>::decode_borrowed(config, bytes)?;
+                    map.insert(entry.0, entry.1);
+                }
+
+                Ok(map)
+            }
+        }
+    };
+}
+
+#[cfg(feature = "std")]
+impl_map!(
+    HashMap<K: Eq + Hash, V, S: BuildHasher + Default>,
+    len,
+    HashMap::with_capacity_and_hasher(len, S::default())
+);
+impl_map!(BTreeMap<K: Ord, V>, len, BTreeMap::new());
+
+macro_rules! impl_fixed_arr {
+    ($($len: expr),+) => {
+        $(
+            impl<'de, T> DecodeBorrowed<'de> for [T; $len]
+            where
+                T: DecodeBorrowed<'de>,
+            {
+                fn decode_borrowed(config: &Config, bytes: &mut &'de [u8]) -> Result<Self> {
+                    let mut values = Vec::with_capacity($len);
+
+                    for _ in 0..$len {
+                        values.push(T::decode_borrowed(config, bytes)?);
+                    }
+
+                    Self::try_from(values).map_err(|_| Error::PartiallyFilledArray)
+                }
+            }
+        )+
+    };
+}
+
+impl_fixed_arr!(
+    1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26,
+    27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50,
+    51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63, 64, 128, 256, 512, 1024
+);
+
+macro_rules! impl_tuple {
+    ($(($($name:tt)+))+) => {
+        $(
+            impl<'de, $($name),+> DecodeBorrowed<'de> for ($($name,)+)
+            where
+                $($name: DecodeBorrowed<'de>,)+
+            {
+                #[inline]
+                fn decode_borrowed(config: &Config, bytes: &mut &'de [u8]) -> Result<Self> {
+                    Ok((
+                        $(
+                            $name::decode_borrowed(config, bytes)?,
+                        )+
+                    ))
+                }
+            }
+        )+
+    }
+}
+
+impl_tuple! {
+    (T0)
+    (T0 T1)
+    (T0 T1 T2)
+    (T0 T1 T2 T3)
+    (T0 T1 T2 T3 T4)
+    (T0 T1 T2 T3 T4 T5)
+    (T0 T1 T2 T3 T4 T5 T6)
+    (T0 T1 T2 T3 T4 T5 T6 T7)
+    (T0 T1 T2 T3 T4 T5 T6 T7 T8)
+    (T0 T1 T2 T3 T4 T5 T6 T7 T8 T9)
+    (T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10)
+    (T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11)
+    (T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12)
+    (T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13)
+    (T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13 T14)
+    (T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13 T14 T15)
+}
+
+impl<'de, T> DecodeBorrowed<'de> for PhantomData<T>
+where
+    T: ?Sized,
+{
+    #[inline]
+    fn decode_borrowed(_config: &Config, _bytes: &mut &'de [u8]) -> Result<Self> {
+        Ok(Default::default())
+    }
+}
@@ -0,0 +1,210 @@
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use core::{
+    cmp, fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+
+use spin::Mutex;
+
+use crate::{io::Read, Config, Decode, Error, Result};
+
+/// Default maximum size of a value accepted by [`Decoder`] and of a frame's payload accepted by other decoders (8 MiB)
+pub const DEFAULT_MAX_VALUE_SIZE: usize = 8 * 1024 * 1024;
+
+/// Progress of decoding a value with [`Decoder`](struct.Decoder.html)
+#[derive(Debug, PartialEq, Eq)]
+pub enum Progress<T> {
+    /// More bytes are needed to decode the value (contains the number of additional bytes needed before decoding can
+    /// make progress; more bytes may be needed after that)
+    ///
+    /// All the bytes passed to [`feed`](struct.Decoder.html#method.feed) are consumed in this case.
+    NeedMore(usize),
+    /// Value is decoded (contains decoded value and the number of bytes consumed from the last fed chunk)
+    ///
+    /// Bytes after the consumed ones belong to the next value and should be fed again.
+    Done(T, usize),
+}
+
+/// Push-based (sans-IO) decoder which decodes a value from byte chunks of arbitrary sizes
+///
+/// Decoding of a partially received value is suspended when fed bytes run out and resumed where it stopped when more
+/// bytes are fed, so every byte is decoded only once. Unlike `Decode::decode_from()` futures, decoder does not hold any
+/// reader, so it can be used from an event loop and it is always safe to stop feeding bytes to it. Decoder is reset
+/// after each decoded value (or error), so it can be used to decode a sequence of values.
+///
+/// Values larger than [`max_size`](#method.max_size) bytes are rejected with [`Error::ValueTooLarge`].
+pub struct Decoder<T> {
+    config: Config,
+    max_size: usize,
+    state: Option<State<T>>,
+}
+
+/// Suspended decoding of a partially received value
+struct State<T> {
+    future: Pin<Box<dyn Future<Output = Result<T>> + Send>>,
+    input: Arc<Mutex<Input>>,
+}
+
+/// Bytes fed to [`Decoder`] which are shared with the reader of suspended decoding
+#[derive(Debug)]
+struct Input {
+    bytes: Vec<u8>,
+    pos: usize,
+    consumed: usize,
+    needed: usize,
+    max_size: usize,
+}
+
+impl<T: Decode + 'static> Decoder<T> {
+    /// Creates a new decoder using default configuration
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_config(Config::default())
+    }
+
+    /// Creates a new decoder using given configuration
+    #[inline]
+    pub fn with_config(config: Config) -> Self {
+        Self {
+            config,
+            max_size: DEFAULT_MAX_VALUE_SIZE,
+            state: None,
+        }
+    }
+
+    /// Sets maximum size of a value accepted by decoder (defaults to [`DEFAULT_MAX_VALUE_SIZE`])
+    #[inline]
+    pub fn with_max_size(mut self, max_size: usize) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Returns maximum size of a value accepted by decoder
+    #[inline]
+    pub fn max_size(&self) -> usize {
+        self.max_size
+    }
+
+    /// Returns `true` if a value is partially decoded
+    #[inline]
+    pub fn is_decoding(&self) -> bool {
+        self.state.is_some()
+    }
+
+    /// Discards partially decoded value
+    #[inline]
+    pub fn reset(&mut self) {
+        self.state = None;
+    }
+
+    /// Feeds a chunk of bytes to decoder and returns the progress of decoding current value
+    pub fn feed(&mut self, bytes: &[u8]) -> Result<Progress<T>> {
+        let state = self.state.get_or_insert_with(|| {
+            let input = Arc::new(Mutex::new(Input {
+                bytes: Vec::new(),
+                pos: 0,
+                consumed: 0,
+                needed: 0,
+                max_size: self.max_size,
+            }));
+            let reader = InputReader(input.clone());
+            let config = self.config.clone();
+
+            State {
+                future: Box::pin(async move { T::decode_from(&config, reader).await }),
+                input,
+            }
+        });
+
+        {
+            let mut input = state.input.lock();
+            input.bytes.clear();
+            input.bytes.extend_from_slice(bytes);
+            input.pos = 0;
+        }
+
+        match state
+            .future
+            .as_mut()
+            .poll(&mut Context::from_waker(Waker::noop()))
+        {
+            Poll::Ready(result) => {
+                let consumed = state.input.lock().pos;
+                self.reset();
+
+                result.map(|value| Progress::Done(value, consumed))
+            }
+            Poll::Pending => {
+                let mut input = state.input.lock();
+                input.bytes.clear();
+
+                Ok(Progress::NeedMore(input.needed))
+            }
+        }
+    }
+}
+
+impl<T: Decode + 'static> Default for Decoder<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> fmt::Debug for Decoder<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Decoder")
+            .field("config", &self.config)
+            .field("max_size", &self.max_size)
+            .field("decoding", &self.state.is_some())
+            .finish()
+    }
+}
+
+/// Reader of suspended decoding which reads fed bytes and returns `Poll::Pending` when they run out
+struct InputReader(Arc<Mutex<Input>>);
+
+impl Read for InputReader {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<Result<usize>> {
+        let mut input = self.0.lock();
+        let input = &mut *input;
+
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        // Bytes after `max_size` can never be a part of an accepted value
+        let size = input.consumed.saturating_add(buf.len());
+
+        if input.consumed >= input.max_size
+            || (input.pos == input.bytes.len() && size > input.max_size)
+        {
+            return Poll::Ready(Err(Error::ValueTooLarge {
+                size,
+                max: input.max_size,
+            }));
+        }
+
+        let len = cmp::min(
+            cmp::min(input.bytes.len() - input.pos, buf.len()),
+            input.max_size - input.consumed,
+        );
+
+        if len == 0 {
+            input.needed = buf.len();
+            return Poll::Pending;
+        }
+
+        buf[..len].copy_from_slice(&input.bytes[input.pos..input.pos + len]);
+        input.pos += len;
+        input.consumed += len;
+
+        Poll::Ready(Ok(len))
+    }
+}
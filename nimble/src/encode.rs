@@ -1,50 +1,178 @@
+use alloc::{
+    borrow::{Cow, ToOwned},
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet, BinaryHeap, LinkedList, VecDeque},
+    ffi::CString,
+    string::String,
+    sync::Arc,
+    vec::Vec,
+};
+use core::ffi::CStr;
+#[cfg(feature = "std")]
+use core::hash::BuildHasher;
 use core::{
     convert::TryFrom,
-    hash::BuildHasher,
+    future::Future,
     marker::PhantomData,
     num::{
         NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
         NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
     },
 };
-use std::{
-    borrow::Cow,
-    collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque},
-    ffi::{CStr, CString},
-    sync::Arc,
-};
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
+
+#[cfg(feature = "bytes")]
+use bytes::{Bytes, BytesMut};
 
 use crate::{
-    async_trait,
-    io::{Write, WriteExt},
+    io::{poll_in_memory, Write, WriteExt},
+    marker::{BoxFuture, DynWrite},
     Config, Endianness, Result, VarInt,
 };
 
-#[async_trait]
 /// Trait for encoding values
 pub trait Encode {
+    /// Size of encoded byte array of every value of this type (`None` if encoded size varies between values)
+    ///
+    /// Sequence types use this to calculate their [`size`](#tymethod.size) in constant time. Types declaring a fixed
+    /// size must return the same value from `size()` for every value.
+    const FIXED_SIZE: Option<usize> = None;
+
     /// Returns size of encoded byte array
     fn size(&self) -> usize;
 
     /// Writes encoded byte array to writer and returns the number of bytes written
     ///
+    /// Implementations can use `async fn` syntax as long as the returned future is `Send` (see `LocalEncode` for
+    /// single-threaded counterpart).
+    ///
     /// ## Equivalent to:
     ///
     /// ```rust,ignore
-    /// async fn encode_to<W>(&self, writer: W) -> Result<usize>
+    /// async fn encode_to<W>(&self, config: &Config, writer: W) -> Result<usize>
     /// where
     ///     W: Write + Unpin + Send
     /// ```
-    async fn encode_to<W>(&self, config: &Config, writer: W) -> Result<usize>
+    fn encode_to<W>(
+        &self,
+        config: &Config,
+        writer: W,
+    ) -> impl Future<Output = Result<usize>> + Send
     where
         W: Write + Unpin + Send;
+
+    /// Appends encoded byte array to `buf` synchronously and returns the number of bytes written
+    ///
+    /// Produces the same bytes as [`encode_to`](#tymethod.encode_to). Default implementation drives `encode_to` to
+    /// completion without an executor (writing to a `Vec` never blocks). Implementations should override this method to
+    /// avoid going through `async` machinery for in-memory encoding.
+    fn encode_to_vec(&self, config: &Config, buf: &mut Vec<u8>) -> usize {
+        poll_in_memory(self.encode_to(config, buf))
+            .expect("Writing to a `Vec` never fails. Log an issue on nimble's GitHub repository with backtrace.")
+    }
+
+    /// Writes encoded byte arrays of all the values in `slice` (without length prefix) to writer and returns the number
+    /// of bytes written
+    ///
+    /// Used by sequence types to encode their elements. Default implementation encodes values one by one. Types which
+    /// can encode multiple values at once (for example, `u8`) override this method.
+    fn encode_slice_to<W>(
+        slice: &[Self],
+        config: &Config,
+        mut writer: W,
+    ) -> impl Future<Output = Result<usize>> + Send
+    where
+        Self: Sized + Sync,
+        W: Write + Unpin + Send,
+    {
+        async move {
+            let mut encoded = 0;
+
+            for item in slice {
+                encoded += item.encode_to(config, &mut writer).await?;
+            }
+
+            Ok(encoded)
+        }
+    }
+
+    /// Appends encoded byte arrays of all the values in `slice` (without length prefix) to `buf` synchronously and
+    /// returns the number of bytes written
+    fn encode_slice_to_vec(slice: &[Self], config: &Config, buf: &mut Vec<u8>) -> usize
+    where
+        Self: Sized,
+    {
+        slice
+            .iter()
+            .map(|item| item.encode_to_vec(config, buf))
+            .sum()
+    }
+}
+
+/// Returns sum of two fixed sizes (`None` if any of them is `None`)
+#[inline]
+pub const fn sum_fixed_sizes(a: Option<usize>, b: Option<usize>) -> Option<usize> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a + b),
+        _ => None,
+    }
+}
+
+/// Returns size of encoded byte arrays of all the elements (calculated in constant time if elements have a fixed size)
+#[inline]
+pub(crate) fn elements_size<'a, T, I>(len: usize, elements: I) -> usize
+where
+    T: Encode + 'a,
+    I: Iterator<Item = &'a T>,
+{
+    match T::FIXED_SIZE {
+        Some(size) => len * size,
+        None => elements.map(Encode::size).sum(),
+    }
+}
+
+/// Size of buffer used to byte swap slices of primitives whose endianness does not match target platform
+const SWAP_BUF_SIZE: usize = 256;
+
+/// Writes bytes of primitive values with `config.endianness` to writer and returns the number of bytes written (values
+/// are byte swapped through a buffer on stack if endianness does not match target platform)
+pub(crate) async fn write_values<T, W>(
+    values: &[T],
+    config: &Config,
+    mut writer: W,
+) -> Result<usize>
+where
+    T: bytemuck::Pod + Sync,
+    W: Write + Unpin,
+{
+    let bytes: &[u8] = bytemuck::cast_slice(values);
+
+    if config.endianness.is_native() {
+        writer.write_all(bytes).await?;
+        return Ok(bytes.len());
+    }
+
+    let size = core::mem::size_of::<T>();
+    let mut buf = [0u8; SWAP_BUF_SIZE];
+
+    for chunk in bytes.chunks(SWAP_BUF_SIZE / size * size) {
+        let buf = &mut buf[..chunk.len()];
+        buf.copy_from_slice(chunk);
+        buf.chunks_exact_mut(size).for_each(<[u8]>::reverse);
+
+        writer.write_all(buf).await?;
+    }
+
+    Ok(bytes.len())
 }
 
 macro_rules! impl_primitive {
     ($($type: tt),+) => {
         $(
-            #[async_trait]
             impl Encode for $type {
+                const FIXED_SIZE: Option<usize> = Some(core::mem::size_of::<Self>());
+
                 #[inline]
                 fn size(&self) -> usize {
                     core::mem::size_of::<Self>()
@@ -54,20 +182,101 @@ macro_rules! impl_primitive {
                 where
                     W: Write + Unpin + Send,
                 {
+                    let bytes = match config.endianness {
+                        Endianness::LittleEndian => self.to_le_bytes(),
+                        Endianness::BigEndian => self.to_be_bytes(),
+                    };
+
+                    writer.write_all(&bytes).await?;
+                    Ok(bytes.len())
+                }
+
+                #[inline]
+                fn encode_to_vec(&self, config: &Config, buf: &mut Vec<u8>) -> usize {
+                    let bytes = match config.endianness {
+                        Endianness::LittleEndian => self.to_le_bytes(),
+                        Endianness::BigEndian => self.to_be_bytes(),
+                    };
+
+                    buf.extend_from_slice(&bytes);
+                    bytes.len()
+                }
+
+                async fn encode_slice_to<W>(slice: &[Self], config: &Config, writer: W) -> Result<usize>
+                where
+                    W: Write + Unpin + Send,
+                {
+                    write_values(slice, config, writer).await
+                }
+
+                fn encode_slice_to_vec(slice: &[Self], config: &Config, buf: &mut Vec<u8>) -> usize {
+                    if config.endianness.is_native() {
+                        let bytes: &[u8] = bytemuck::cast_slice(slice);
+                        buf.extend_from_slice(bytes);
+                        return bytes.len();
+                    }
+
+                    let encoded = slice.len() * core::mem::size_of::<Self>();
+                    buf.reserve(encoded);
+
                     match config.endianness {
-                        Endianness::LittleEndian => writer.write(&self.to_le_bytes()).await.map_err(Into::into),
-                        Endianness::BigEndian => writer.write(&self.to_be_bytes()).await.map_err(Into::into)
+                        Endianness::LittleEndian => {
+                            slice.iter().for_each(|value| buf.extend_from_slice(&value.to_le_bytes()))
+                        }
+                        Endianness::BigEndian => {
+                            slice.iter().for_each(|value| buf.extend_from_slice(&value.to_be_bytes()))
+                        }
                     }
+
+                    encoded
                 }
             }
         )+
     };
 }
 
-impl_primitive!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, usize, isize, f32, f64);
+impl_primitive!(u16, u32, u64, u128, i8, i16, i32, i64, i128, usize, isize, f32, f64);
+
+impl Encode for u8 {
+    const FIXED_SIZE: Option<usize> = Some(core::mem::size_of::<u8>());
+
+    #[inline]
+    fn size(&self) -> usize {
+        core::mem::size_of::<u8>()
+    }
+
+    async fn encode_to<W>(&self, _config: &Config, mut writer: W) -> Result<usize>
+    where
+        W: Write + Unpin + Send,
+    {
+        writer.write_all(&[*self]).await?;
+        Ok(1)
+    }
+
+    #[inline]
+    fn encode_to_vec(&self, _config: &Config, buf: &mut Vec<u8>) -> usize {
+        buf.push(*self);
+        1
+    }
+
+    async fn encode_slice_to<W>(slice: &[Self], _config: &Config, mut writer: W) -> Result<usize>
+    where
+        W: Write + Unpin + Send,
+    {
+        writer.write_all(slice).await?;
+        Ok(slice.len())
+    }
+
+    #[inline]
+    fn encode_slice_to_vec(slice: &[Self], _config: &Config, buf: &mut Vec<u8>) -> usize {
+        buf.extend_from_slice(slice);
+        slice.len()
+    }
+}
 
-#[async_trait]
 impl Encode for bool {
+    const FIXED_SIZE: Option<usize> = Some(core::mem::size_of::<bool>());
+
     #[inline]
     fn size(&self) -> usize {
         core::mem::size_of::<bool>()
@@ -80,10 +289,17 @@ impl Encode for bool {
     {
         (*self as u8).encode_to(config, writer).await
     }
+
+    #[inline]
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    fn encode_to_vec(&self, config: &Config, buf: &mut Vec<u8>) -> usize {
+        (*self as u8).encode_to_vec(config, buf)
+    }
 }
 
-#[async_trait]
 impl Encode for char {
+    const FIXED_SIZE: Option<usize> = Some(core::mem::size_of::<char>());
+
     #[inline]
     fn size(&self) -> usize {
         core::mem::size_of::<char>()
@@ -96,9 +312,14 @@ impl Encode for char {
     {
         (*self as u32).encode_to(config, writer).await
     }
+
+    #[inline]
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    fn encode_to_vec(&self, config: &Config, buf: &mut Vec<u8>) -> usize {
+        (*self as u32).encode_to_vec(config, buf)
+    }
 }
 
-#[async_trait]
 impl<T> Encode for Option<T>
 where
     T: Encode + Sync,
@@ -115,14 +336,20 @@ where
         W: Write + Unpin + Send,
     {
         match self {
-            None => 0u8.encode_to(config, &mut writer).await.map_err(Into::into),
+            None => 0u8.encode_to(config, &mut writer).await,
             Some(ref value) => Ok(1u8.encode_to(config, &mut writer).await?
                 + value.encode_to(config, &mut writer).await?),
         }
     }
+
+    fn encode_to_vec(&self, config: &Config, buf: &mut Vec<u8>) -> usize {
+        match self {
+            None => 0u8.encode_to_vec(config, buf),
+            Some(ref value) => 1u8.encode_to_vec(config, buf) + value.encode_to_vec(config, buf),
+        }
+    }
 }
 
-#[async_trait]
 impl<T, E> Encode for core::result::Result<T, E>
 where
     T: Encode + Sync,
@@ -150,11 +377,17 @@ where
             }
         }
     }
+
+    fn encode_to_vec(&self, config: &Config, buf: &mut Vec<u8>) -> usize {
+        match self {
+            Ok(ref value) => 0u8.encode_to_vec(config, buf) + value.encode_to_vec(config, buf),
+            Err(ref err) => 1u8.encode_to_vec(config, buf) + err.encode_to_vec(config, buf),
+        }
+    }
 }
 
 macro_rules! impl_seq {
     ($ty: tt < T $(: $tbound1: tt $(+ $tbound2: ident)*)* $(, $typaram: tt : $bound1: tt $(+ $bound2: tt)*)* >) => {
-        #[async_trait]
         impl<T $(, $typaram)*> Encode for $ty<T $(, $typaram)*>
         where
             T: Encode + Sync $(+ $tbound1 $(+ $tbound2)*)*,
@@ -162,7 +395,7 @@ macro_rules! impl_seq {
         {
             #[inline]
             fn size(&self) -> usize {
-                VarInt::try_from(self.len()).expect("Failed to convert usize to u128. Log an issue on nimble's GitHub repository with backtrace.").size() + self.iter().map(Encode::size).sum::<usize>()
+                VarInt::try_from(self.len()).expect("Failed to convert usize to u128. Log an issue on nimble's GitHub repository with backtrace.").size() + elements_size(self.len(), self.iter())
             }
 
             #[allow(clippy::ptr_arg)]
@@ -181,25 +414,85 @@ macro_rules! impl_seq {
 
                 Ok(encoded)
             }
+
+            #[allow(clippy::ptr_arg)]
+            fn encode_to_vec(&self, config: &Config, buf: &mut Vec<u8>) -> usize {
+                let len = VarInt::try_from(self.len()).expect("Failed to convert usize to u128. Log an issue on nimble's GitHub repository with backtrace.");
+                let mut encoded = len.encode_to_vec(config, buf);
+
+                for item in self.iter() {
+                    encoded += item.encode_to_vec(config, buf);
+                }
+
+                encoded
+            }
         }
     };
 }
 
-impl_seq!(Vec<T>);
-impl_seq!(VecDeque<T>);
 impl_seq!(LinkedList<T>);
+#[cfg(feature = "std")]
 impl_seq!(HashSet<T, S: BuildHasher + Sync>);
 impl_seq!(BTreeSet<T: 'static>);
-impl_seq!(BinaryHeap<T>);
 
-#[async_trait]
+/// Implements `Encode` for sequences whose elements are stored in contiguous slices, which are encoded with
+/// `Encode::encode_slice_to()`
+macro_rules! impl_slices_seq {
+    ($ty: ident, $seq: ident => $slices: expr) => {
+        impl<T> Encode for $ty<T>
+        where
+            T: Encode + Sync,
+        {
+            #[inline]
+            fn size(&self) -> usize {
+                VarInt::try_from(self.len()).expect("Failed to convert usize to u128. Log an issue on nimble's GitHub repository with backtrace.").size() + elements_size(self.len(), self.iter())
+            }
+
+            async fn encode_to<W>(&self, config: &Config, mut writer: W) -> Result<usize>
+            where
+                W: Write + Unpin + Send,
+            {
+                let len = VarInt::try_from(self.len()).expect("Failed to convert usize to u128. Log an issue on nimble's GitHub repository with backtrace.");
+                let mut encoded = len.encode_to(config, &mut writer).await?;
+
+                let $seq = self;
+
+                for slice in $slices {
+                    encoded += T::encode_slice_to(slice, config, &mut writer).await?;
+                }
+
+                Ok(encoded)
+            }
+
+            fn encode_to_vec(&self, config: &Config, buf: &mut Vec<u8>) -> usize {
+                let len = VarInt::try_from(self.len()).expect("Failed to convert usize to u128. Log an issue on nimble's GitHub repository with backtrace.");
+                let mut encoded = len.encode_to_vec(config, buf);
+
+                let $seq = self;
+
+                for slice in $slices {
+                    encoded += T::encode_slice_to_vec(slice, config, buf);
+                }
+
+                encoded
+            }
+        }
+    };
+}
+
+impl_slices_seq!(VecDeque, seq => {
+    let (front, back) = seq.as_slices();
+    [front, back]
+});
+impl_slices_seq!(BinaryHeap, seq => [seq.as_slice()]);
+
 impl<T> Encode for [T]
 where
     T: Encode + Sync,
 {
     #[inline]
     fn size(&self) -> usize {
-        VarInt::try_from(self.len()).expect("Failed to convert usize to u128. Log an issue on nimble's GitHub repository with backtrace.").size() + self.iter().map(Encode::size).sum::<usize>()
+        VarInt::try_from(self.len()).expect("Failed to convert usize to u128. Log an issue on nimble's GitHub repository with backtrace.").size() + elements_size(self.len(), self.iter())
     }
 
     async fn encode_to<W>(&self, config: &Config, mut writer: W) -> Result<usize>
@@ -210,18 +503,41 @@ where
 
         let len = VarInt::try_from(self.len()).expect("Failed to convert usize to u128. Log an issue on nimble's GitHub repository with backtrace.");
         encoded += len.encode_to(config, &mut writer).await?;
-
-        for item in self.iter() {
-            encoded += item.encode_to(config, &mut writer).await?;
-        }
+        encoded += T::encode_slice_to(self, config, &mut writer).await?;
 
         Ok(encoded)
     }
+
+    fn encode_to_vec(&self, config: &Config, buf: &mut Vec<u8>) -> usize {
+        let len = VarInt::try_from(self.len()).expect("Failed to convert usize to u128. Log an issue on nimble's GitHub repository with backtrace.");
+        len.encode_to_vec(config, buf) + T::encode_slice_to_vec(self, config, buf)
+    }
+}
+
+impl<T> Encode for Vec<T>
+where
+    T: Encode + Sync,
+{
+    #[inline]
+    fn size(&self) -> usize {
+        self.as_slice().size()
+    }
+
+    async fn encode_to<W>(&self, config: &Config, writer: W) -> Result<usize>
+    where
+        W: Write + Unpin + Send,
+    {
+        self.as_slice().encode_to(config, writer).await
+    }
+
+    #[inline]
+    fn encode_to_vec(&self, config: &Config, buf: &mut Vec<u8>) -> usize {
+        self.as_slice().encode_to_vec(config, buf)
+    }
 }
 
 macro_rules! impl_as_bytes {
     ($ty: tt, $as_bytes: tt) => {
-        #[async_trait]
         impl Encode for $ty {
             #[inline]
             fn size(&self) -> usize {
@@ -235,6 +551,12 @@ macro_rules! impl_as_bytes {
             {
                 Self::$as_bytes(self).encode_to(config, writer).await
             }
+
+            #[inline]
+            #[allow(clippy::ptr_arg)]
+            fn encode_to_vec(&self, config: &Config, buf: &mut Vec<u8>) -> usize {
+                Self::$as_bytes(self).encode_to_vec(config, buf)
+            }
         }
     };
 }
@@ -243,11 +565,16 @@ impl_as_bytes!(str, as_bytes);
 impl_as_bytes!(String, as_bytes);
 impl_as_bytes!(CStr, to_bytes);
 impl_as_bytes!(CString, as_bytes);
+#[cfg(feature = "bytes")]
+impl_as_bytes!(Bytes, as_ref);
+#[cfg(feature = "bytes")]
+impl_as_bytes!(BytesMut, as_ref);
 
 macro_rules! impl_deref {
     ($($desc: tt)+) => {
-        #[async_trait]
         impl $($desc)+ {
+            const FIXED_SIZE: Option<usize> = T::FIXED_SIZE;
+
             #[inline]
             fn size(&self) -> usize {
                 <T>::size(self)
@@ -259,21 +586,58 @@ macro_rules! impl_deref {
             {
                 <T>::encode_to(self, config, writer).await
             }
+
+            #[inline]
+            fn encode_to_vec(&self, config: &Config, buf: &mut Vec<u8>) -> usize {
+                <T>::encode_to_vec(self, config, buf)
+            }
         }
     }
 }
 
 impl_deref!(<T: ?Sized> Encode for &T where T: Encode + Sync);
 impl_deref!(<T: ?Sized> Encode for &mut T where T: Encode + Sync);
-impl_deref!(<T: ?Sized> Encode for Box<T> where T: Encode + Sync);
-impl_deref!(<T: ?Sized> Encode for Arc<T> where T: Encode + Sync + Send);
 
-#[async_trait]
+/// Smart pointers are used as indirection in recursive types. So, future of inner value is boxed (to keep size of the
+/// future finite) and writer is passed as a trait object (to keep number of monomorphized functions finite).
+macro_rules! impl_boxed_deref {
+    ($($desc: tt)+) => {
+        impl $($desc)+ {
+            const FIXED_SIZE: Option<usize> = T::FIXED_SIZE;
+
+            #[inline]
+            fn size(&self) -> usize {
+                <T>::size(self)
+            }
+
+            async fn encode_to<W>(&self, config: &Config, mut writer: W) -> Result<usize>
+            where
+                W: Write + Unpin + Send,
+            {
+                let writer: &mut DynWrite<'_> = &mut writer;
+                let future: BoxFuture<'_, Result<usize>> =
+                    Box::pin(<T>::encode_to(self, config, writer));
+                future.await
+            }
+
+            #[inline]
+            fn encode_to_vec(&self, config: &Config, buf: &mut Vec<u8>) -> usize {
+                <T>::encode_to_vec(self, config, buf)
+            }
+        }
+    }
+}
+
+impl_boxed_deref!(<T: ?Sized> Encode for Box<T> where T: Encode + Sync);
+impl_boxed_deref!(<T: ?Sized> Encode for Arc<T> where T: Encode + Sync + Send);
+
 impl<T: ?Sized> Encode for Cow<'_, T>
 where
     T: Encode + ToOwned + Sync,
     <T as ToOwned>::Owned: Sync,
 {
+    const FIXED_SIZE: Option<usize> = T::FIXED_SIZE;
+
     #[inline]
     fn size(&self) -> usize {
         self.as_ref().size()
@@ -286,11 +650,16 @@ where
     {
         self.as_ref().encode_to(config, writer).await
     }
+
+    #[inline]
+    #[allow(clippy::ptr_arg)]
+    fn encode_to_vec(&self, config: &Config, buf: &mut Vec<u8>) -> usize {
+        self.as_ref().encode_to_vec(config, buf)
+    }
 }
 
 macro_rules! impl_map {
     ($ty: tt < K $(: $kbound1: tt $(+ $kbound2: tt)*)*, V $(: $vbound1: tt $(+ $vbound2: tt)*)* $(, $typaram: tt : $bound1: tt $(+ $bound2: tt)*)* >) => {
-        #[async_trait]
         impl<K, V $(, $typaram)*> Encode for $ty<K, V $(, $typaram)*>
         where
             K: Encode + Sync $(+ $kbound1 $(+ $kbound2)*)*,
@@ -299,7 +668,10 @@ macro_rules! impl_map {
         {
             #[inline]
             fn size(&self) -> usize {
-                VarInt::try_from(self.len()).expect("Failed to convert usize to u128. Log an issue on nimble's GitHub repository with backtrace.").size() + self.iter().map(|entry| entry.size()).sum::<usize>()
+                VarInt::try_from(self.len()).expect("Failed to convert usize to u128. Log an issue on nimble's GitHub repository with backtrace.").size() + match (K::FIXED_SIZE, V::FIXED_SIZE) {
+                    (Some(key_size), Some(value_size)) => self.len() * (key_size + value_size),
+                    _ => self.iter().map(|entry| entry.size()).sum::<usize>(),
+                }
             }
 
             async fn encode_to<W>(&self, config: &Config, mut writer: W) -> Result<usize>
@@ -317,37 +689,52 @@ macro_rules! impl_map {
 
                 Ok(encoded)
             }
+
+            #[allow(clippy::ptr_arg)]
+            fn encode_to_vec(&self, config: &Config, buf: &mut Vec<u8>) -> usize {
+                let len = VarInt::try_from(self.len()).expect("Failed to convert usize to u128. Log an issue on nimble's GitHub repository with backtrace.");
+                let mut encoded = len.encode_to_vec(config, buf);
+
+                for item in self.iter() {
+                    encoded += item.encode_to_vec(config, buf);
+                }
+
+                encoded
+            }
         }
     };
 }
 
+#[cfg(feature = "std")]
 impl_map!(HashMap<K, V, S: BuildHasher + Sync>);
 impl_map!(BTreeMap<K: 'static, V: 'static>);
 
 macro_rules! impl_fixed_arr {
     ($($len: tt),+) => {
         $(
-            #[async_trait]
             impl<T> Encode for [T; $len]
             where
                 T: Encode + Sync,
             {
+                const FIXED_SIZE: Option<usize> = match T::FIXED_SIZE {
+                    Some(size) => Some($len * size),
+                    None => None,
+                };
+
                 #[inline]
                 fn size(&self) -> usize {
-                    self.iter().map(Encode::size).sum::<usize>()
+                    elements_size($len, self.iter())
                 }
 
-                async fn encode_to<W>(&self, config: &Config, mut writer: W) -> Result<usize>
+                async fn encode_to<W>(&self, config: &Config, writer: W) -> Result<usize>
                 where
                     W: Write + Unpin + Send,
                 {
-                    let mut encoded = 0;
-
-                    for item in self.iter() {
-                        encoded += item.encode_to(config, &mut writer).await?;
-                    }
+                    T::encode_slice_to(self, config, writer).await
+                }
 
-                    Ok(encoded)
+                fn encode_to_vec(&self, config: &Config, buf: &mut Vec<u8>) -> usize {
+                    T::encode_slice_to_vec(self, config, buf)
                 }
             }
         )+
@@ -360,8 +747,9 @@ impl_fixed_arr!(
     51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63, 64, 128, 256, 512, 1024
 );
 
-#[async_trait]
 impl Encode for () {
+    const FIXED_SIZE: Option<usize> = Some(0);
+
     #[inline]
     fn size(&self) -> usize {
         0
@@ -374,16 +762,27 @@ impl Encode for () {
     {
         Ok(0)
     }
+
+    #[inline]
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    fn encode_to_vec(&self, _config: &Config, _buf: &mut Vec<u8>) -> usize {
+        0
+    }
 }
 
 macro_rules! impl_tuple {
     ($(($($n:tt $name:tt)+))+) => {
         $(
-            #[async_trait]
             impl<$($name),+> Encode for ($($name,)+)
             where
                 $($name: Encode + Send + Sync,)+
             {
+                const FIXED_SIZE: Option<usize> = {
+                    let size = Some(0);
+                    $(let size = sum_fixed_sizes(size, $name::FIXED_SIZE);)+
+                    size
+                };
+
                 #[inline]
                 fn size(&self) -> usize {
                     0 $(+ self.$n.size())+
@@ -401,6 +800,11 @@ macro_rules! impl_tuple {
 
                     Ok(encoded)
                 }
+
+                #[inline]
+                fn encode_to_vec(&self, config: &Config, buf: &mut Vec<u8>) -> usize {
+                    0 $(+ self.$n.encode_to_vec(config, buf))+
+                }
             }
         )+
     }
@@ -425,11 +829,12 @@ impl_tuple! {
     (0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7 8 T8 9 T9 10 T10 11 T11 12 T12 13 T13 14 T14 15 T15)
 }
 
-#[async_trait]
 impl<T> Encode for PhantomData<T>
 where
     T: Send + Sync + ?Sized,
 {
+    const FIXED_SIZE: Option<usize> = Some(0);
+
     #[inline]
     fn size(&self) -> usize {
         0
@@ -442,13 +847,20 @@ where
     {
         Ok(0)
     }
+
+    #[inline]
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    fn encode_to_vec(&self, _config: &Config, _buf: &mut Vec<u8>) -> usize {
+        0
+    }
 }
 
 macro_rules! impl_non_zero_primitives {
     ($($type: tt),+) => {
         $(
-            #[async_trait]
             impl Encode for $type {
+                const FIXED_SIZE: Option<usize> = Some(core::mem::size_of::<Self>());
+
                 #[inline]
                 fn size(&self) -> usize {
                     core::mem::size_of::<Self>()
@@ -460,6 +872,11 @@ macro_rules! impl_non_zero_primitives {
                 {
                     self.get().encode_to(config, writer).await
                 }
+
+                #[inline]
+                fn encode_to_vec(&self, config: &Config, buf: &mut Vec<u8>) -> usize {
+                    self.get().encode_to_vec(config, buf)
+                }
             }
         )+
     };
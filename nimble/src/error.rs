@@ -5,31 +5,101 @@ use crate::VarInt;
 #[derive(Debug, Error)]
 /// Error returned by this crate
 pub enum Error {
+    /// Buffer is too small to hold encoded bytes
+    #[error("Buffer too small: {needed} bytes needed")]
+    BufferTooSmall {
+        /// Number of bytes needed to encode the value
+        needed: usize,
+    },
+    /// Frame is larger than maximum frame size
+    #[error("Frame too large: {size} bytes (maximum: {max} bytes)")]
+    FrameTooLarge {
+        /// Size of frame
+        size: usize,
+        /// Maximum frame size
+        max: usize,
+    },
     /// Invalid character
     #[error("Invalid character: {0}")]
     InvalidChar(u32),
     /// Invalid enum variant
     #[error("Invalid enum variant: {0}")]
     InvalidEnumVariant(VarInt),
+    /// Frame does not start with magic bytes
+    #[error("Invalid frame magic bytes: {0:?}")]
+    InvalidFrameMagic([u8; 4]),
+    /// Encoded `VarInt` is longer than maximum encoded length or does not fit in `u128`
+    #[error("Invalid VarInt")]
+    InvalidVarInt,
     /// Invalid UTF-8 string
     #[error("Invalid UTF-8 string: {0}")]
-    InvalidUtf8String(#[from] std::string::FromUtf8Error),
+    InvalidUtf8String(#[from] alloc::string::FromUtf8Error),
+    /// Invalid UTF-8 string slice
+    #[error("Invalid UTF-8 string slice: {0}")]
+    InvalidUtf8Str(#[from] core::str::Utf8Error),
     /// IO error
+    #[cfg(feature = "std")]
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
+    /// `embedded-io` error
+    #[cfg(feature = "embedded-io")]
+    #[error("Embedded IO error: {0:?}")]
+    EmbeddedIoError(embedded_io::ErrorKind),
+    /// Number of encoded sequence elements does not match declared length
+    #[error("Sequence length mismatch: expected {expected} elements, got {actual}")]
+    LengthMismatch {
+        /// Declared length of sequence
+        expected: usize,
+        /// Number of encoded elements
+        actual: usize,
+    },
     /// A non-zero value is zero
     #[error("A non-zero value is zero")]
     NonZeroError,
     /// CString contains trailing 0 byte
     #[error("CString contains trailing 0 byte: {0}")]
-    NulError(#[from] std::ffi::NulError),
+    NulError(#[from] alloc::ffi::NulError),
     /// Partially filled array
     #[error("Partially filled array")]
     PartiallyFilledArray,
+    /// Bytes left in frame payload after decoding value
+    #[error("Frame payload has {0} trailing bytes")]
+    TrailingBytes(usize),
     /// Failed to do integral type conversion
     #[error("Failed to do integral type conversion: {0}")]
     TryFromIntError(#[from] core::num::TryFromIntError),
+    /// Reader reached end of source before filling the buffer
+    #[error("Unexpected end of source")]
+    UnexpectedEof,
+    /// Version of frame format is not supported
+    #[error("Unsupported frame version: {0}")]
+    UnsupportedFrameVersion(u8),
+    /// Value is larger than maximum size accepted by [`Decoder`](struct.Decoder.html)
+    #[error("Value too large: at least {size} bytes (maximum: {max} bytes)")]
+    ValueTooLarge {
+        /// Number of bytes needed to decode the value
+        size: usize,
+        /// Maximum value size
+        max: usize,
+    },
+    /// Writer failed to write the whole buffer
+    #[error("Failed to write the whole buffer")]
+    WriteZero,
 }
 
 /// Result type with [`nimble::Error`](enum.Error.html)
 pub type Result<T> = core::result::Result<T, Error>;
+
+#[cfg(feature = "std")]
+impl From<Error> for std::io::Error {
+    fn from(error: Error) -> Self {
+        use std::io::ErrorKind;
+
+        match error {
+            Error::IoError(error) => error,
+            Error::UnexpectedEof => ErrorKind::UnexpectedEof.into(),
+            Error::WriteZero => ErrorKind::WriteZero.into(),
+            error => Self::new(ErrorKind::InvalidData, error),
+        }
+    }
+}
@@ -0,0 +1,138 @@
+//! Framed message format with a header identifying the format
+//!
+//! Each frame consists of a header followed by the payload (encoded value):
+//!
+//! | Field          | Size (bytes) | Description                                            |
+//! |----------------|--------------|--------------------------------------------------------|
+//! | Magic          | 4            | [`MAGIC`] (`b"NMBL"`)                                  |
+//! | Version        | 1            | Version of frame format ([`VERSION`])                  |
+//! | Flags          | 1            | Application defined flags                              |
+//! | Payload length | 4            | Size of payload in bytes (big endian `u32`)            |
+//! | Payload        | variable     | Value encoded using [`Config`](../struct.Config.html)  |
+//!
+//! Header is always encoded in the same way (irrespective of [`Config`](../struct.Config.html)), so that independent
+//! services agree on the bytes.
+use alloc::{vec, vec::Vec};
+use core::convert::TryFrom;
+
+use crate::{
+    io::{Read, ReadExt, Write, WriteExt},
+    Config, Decode, Encode, Error, Result, DEFAULT_MAX_VALUE_SIZE,
+};
+
+/// Magic bytes at the start of every frame
+pub const MAGIC: [u8; 4] = *b"NMBL";
+
+/// Current version of frame format
+pub const VERSION: u8 = 1;
+
+/// Number of bytes in frame header
+pub const HEADER_LEN: usize = MAGIC.len() + 1 + 1 + 4;
+
+/// Default maximum size of payload accepted by [`read_frame`] (same as
+/// [`DEFAULT_MAX_VALUE_SIZE`](../constant.DEFAULT_MAX_VALUE_SIZE.html))
+pub const DEFAULT_MAX_PAYLOAD_SIZE: usize = DEFAULT_MAX_VALUE_SIZE;
+
+/// Decoded frame
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame<T> {
+    /// Application defined flags
+    pub flags: u8,
+    /// Decoded payload
+    pub value: T,
+}
+
+/// Writes a frame containing encoded `value` to writer and returns the number of bytes written (including header)
+///
+/// Returns [`Error::FrameTooLarge`](../enum.Error.html#variant.FrameTooLarge) if encoded value does not fit in a frame.
+pub async fn write_frame<E, W>(
+    config: &Config,
+    flags: u8,
+    value: &E,
+    mut writer: W,
+) -> Result<usize>
+where
+    E: Encode + ?Sized,
+    W: Write + Unpin + Send,
+{
+    let mut bytes = Vec::with_capacity(HEADER_LEN.saturating_add(value.size()));
+    bytes.extend_from_slice(&MAGIC);
+    bytes.push(VERSION);
+    bytes.push(flags);
+    bytes.extend_from_slice(&[0; 4]);
+
+    // Payload length is taken from the encoded bytes (not `Encode::size()`), so that header always matches payload
+    value.encode_to_vec(config, &mut bytes);
+
+    let size = bytes.len() - HEADER_LEN;
+    let payload_len = u32::try_from(size).map_err(|_| Error::FrameTooLarge {
+        size,
+        max: u32::MAX as usize,
+    })?;
+    bytes[6..HEADER_LEN].copy_from_slice(&payload_len.to_be_bytes());
+
+    writer.write_all(&bytes).await?;
+
+    Ok(bytes.len())
+}
+
+/// Reads a frame from reader and decodes its payload
+///
+/// Returns a typed error if magic bytes do not match ([`Error::InvalidFrameMagic`]), version is not supported
+/// ([`Error::UnsupportedFrameVersion`]), payload is larger than `max_payload_size` ([`Error::FrameTooLarge`]) or decoded
+/// value does not span the whole payload ([`Error::TrailingBytes`]).
+///
+/// [`Error::InvalidFrameMagic`]: ../enum.Error.html#variant.InvalidFrameMagic
+/// [`Error::UnsupportedFrameVersion`]: ../enum.Error.html#variant.UnsupportedFrameVersion
+/// [`Error::FrameTooLarge`]: ../enum.Error.html#variant.FrameTooLarge
+/// [`Error::TrailingBytes`]: ../enum.Error.html#variant.TrailingBytes
+pub async fn read_frame<D, R>(
+    config: &Config,
+    max_payload_size: usize,
+    mut reader: R,
+) -> Result<Frame<D>>
+where
+    D: Decode,
+    R: Read + Unpin + Send,
+{
+    let mut header = [0u8; HEADER_LEN];
+    reader.read_exact(&mut header).await?;
+
+    let mut magic = [0u8; 4];
+    magic.copy_from_slice(&header[..4]);
+
+    if magic != MAGIC {
+        return Err(Error::InvalidFrameMagic(magic));
+    }
+
+    let version = header[4];
+
+    if version != VERSION {
+        return Err(Error::UnsupportedFrameVersion(version));
+    }
+
+    let flags = header[5];
+
+    let mut payload_len = [0u8; 4];
+    payload_len.copy_from_slice(&header[6..]);
+    let size = usize::try_from(u32::from_be_bytes(payload_len))?;
+
+    if size > max_payload_size {
+        return Err(Error::FrameTooLarge {
+            size,
+            max: max_payload_size,
+        });
+    }
+
+    let mut payload = vec![0u8; size];
+    reader.read_exact(&mut payload).await?;
+
+    let mut bytes = &payload[..];
+    let value = D::decode_from_slice(config, &mut bytes)?;
+
+    if !bytes.is_empty() {
+        return Err(Error::TrailingBytes(bytes.len()));
+    }
+
+    Ok(Frame { flags, value })
+}
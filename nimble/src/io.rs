@@ -1,10 +1,385 @@
-//! Re-exports IO traits from `tokio`/`futures` depending on enabled feature.
-#[cfg(not(feature = "tokio"))]
-pub use futures_util::io::{
-    AsyncRead as Read, AsyncReadExt as ReadExt, AsyncWrite as Write, AsyncWriteExt as WriteExt,
+//! Runtime agnostic IO traits used for encoding and decoding values.
+//!
+//! [`Encode`](../trait.Encode.html) and [`Decode`](../trait.Decode.html) are implemented in terms of [`Read`] and
+//! [`Write`] traits defined in this module. Adapters for `futures`, `tokio` and `embedded-io` IO types (and `bytes`'
+//! `BufMut` types and `Stream`s of byte chunks) are provided behind their respective features (any of them can be
+//! enabled at the same time). `embedded-io-async` IO types are supported by `EmbeddedAsyncIo`, which encodes and
+//! decodes whole values instead of implementing [`Read`] and [`Write`].
+mod buf_writer;
+#[cfg(feature = "bytes")]
+mod bytes;
+#[cfg(feature = "embedded-io")]
+mod embedded;
+#[cfg(feature = "embedded-io-async")]
+mod embedded_async;
+#[cfg(feature = "futures")]
+mod futures;
+#[cfg(all(feature = "futures", feature = "bytes"))]
+mod stream_reader;
+#[cfg(feature = "tokio")]
+mod tokio;
+
+use alloc::{boxed::Box, vec::Vec};
+use core::{
+    future::Future,
+    mem,
+    ops::DerefMut,
+    pin::Pin,
+    task::{ready, Context, Poll, Waker},
 };
 
+use crate::{Error, Result};
+
+pub use self::buf_writer::BufWriter;
+#[cfg(feature = "bytes")]
+pub use self::bytes::BufMutWriter;
+#[cfg(feature = "embedded-io")]
+pub use self::embedded::EmbeddedIo;
+#[cfg(feature = "embedded-io-async")]
+pub use self::embedded_async::EmbeddedAsyncIo;
+#[cfg(feature = "futures")]
+pub use self::futures::FuturesIo;
+#[cfg(all(feature = "futures", feature = "bytes"))]
+pub use self::stream_reader::StreamReader;
 #[cfg(feature = "tokio")]
-pub use tokio::io::{
-    AsyncRead as Read, AsyncReadExt as ReadExt, AsyncWrite as Write, AsyncWriteExt as WriteExt,
-};
+pub use self::tokio::TokioIo;
+
+/// Reads bytes from a source asynchronously
+pub trait Read {
+    /// Attempts to read bytes into `buf` and returns the number of bytes read (`0` means end of the source)
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8])
+        -> Poll<Result<usize>>;
+}
+
+/// Writes bytes into a sink asynchronously
+pub trait Write {
+    /// Attempts to write bytes from `buf` and returns the number of bytes written
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<Result<usize>>;
+
+    /// Attempts to flush all the intermediately buffered bytes to their destination
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>>;
+
+    /// Attempts to flush and close the sink
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>>;
+}
+
+macro_rules! impl_deref_read {
+    ($($desc: tt)+) => {
+        impl $($desc)+ {
+            #[inline]
+            fn poll_read(
+                mut self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+                buf: &mut [u8],
+            ) -> Poll<Result<usize>> {
+                Pin::new(&mut **self).poll_read(cx, buf)
+            }
+        }
+    };
+}
+
+impl_deref_read!(<T: ?Sized + Read + Unpin> Read for &mut T);
+impl_deref_read!(<T: ?Sized + Read + Unpin> Read for Box<T>);
+
+macro_rules! impl_deref_write {
+    ($($desc: tt)+) => {
+        impl $($desc)+ {
+            #[inline]
+            fn poll_write(
+                mut self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+                buf: &[u8],
+            ) -> Poll<Result<usize>> {
+                Pin::new(&mut **self).poll_write(cx, buf)
+            }
+
+            #[inline]
+            fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+                Pin::new(&mut **self).poll_flush(cx)
+            }
+
+            #[inline]
+            fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+                Pin::new(&mut **self).poll_close(cx)
+            }
+        }
+    };
+}
+
+impl_deref_write!(<T: ?Sized + Write + Unpin> Write for &mut T);
+impl_deref_write!(<T: ?Sized + Write + Unpin> Write for Box<T>);
+
+impl<P> Read for Pin<P>
+where
+    P: DerefMut + Unpin,
+    P::Target: Read,
+{
+    #[inline]
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<Result<usize>> {
+        self.get_mut().as_mut().poll_read(cx, buf)
+    }
+}
+
+impl<P> Write for Pin<P>
+where
+    P: DerefMut + Unpin,
+    P::Target: Write,
+{
+    #[inline]
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<Result<usize>> {
+        self.get_mut().as_mut().poll_write(cx, buf)
+    }
+
+    #[inline]
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.get_mut().as_mut().poll_flush(cx)
+    }
+
+    #[inline]
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.get_mut().as_mut().poll_close(cx)
+    }
+}
+
+impl Read for &[u8] {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<Result<usize>> {
+        let len = core::cmp::min(self.len(), buf.len());
+        let (head, tail) = self.split_at(len);
+
+        buf[..len].copy_from_slice(head);
+        *self = tail;
+
+        Poll::Ready(Ok(len))
+    }
+}
+
+impl Write for &mut [u8] {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize>> {
+        let len = core::cmp::min(self.len(), buf.len());
+        let (head, tail) = core::mem::take(&mut *self).split_at_mut(len);
+
+        head.copy_from_slice(&buf[..len]);
+        *self = tail;
+
+        Poll::Ready(Ok(len))
+    }
+
+    #[inline]
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    #[inline]
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl Write for Vec<u8> {
+    #[inline]
+    fn poll_write(self: Pin<&mut Self>, _cx: &mut Context<'_>, buf: &[u8]) -> Poll<Result<usize>> {
+        self.get_mut().extend_from_slice(buf);
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    #[inline]
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    #[inline]
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Extension trait providing `async` helper functions for [`Read`]
+pub trait ReadExt: Read {
+    /// Reads exact number of bytes required to fill `buf`
+    ///
+    /// ## Equivalent to:
+    ///
+    /// ```rust,ignore
+    /// async fn read_exact(&mut self, buf: &mut [u8]) -> Result<()>
+    /// ```
+    fn read_exact<'a>(&'a mut self, buf: &'a mut [u8]) -> ReadExact<'a, Self>
+    where
+        Self: Unpin,
+    {
+        ReadExact { reader: self, buf }
+    }
+}
+
+impl<R: Read + ?Sized> ReadExt for R {}
+
+/// Extension trait providing `async` helper functions for [`Write`]
+pub trait WriteExt: Write {
+    /// Writes all the bytes in `buf`
+    ///
+    /// ## Equivalent to:
+    ///
+    /// ```rust,ignore
+    /// async fn write_all(&mut self, buf: &[u8]) -> Result<()>
+    /// ```
+    fn write_all<'a>(&'a mut self, buf: &'a [u8]) -> WriteAll<'a, Self>
+    where
+        Self: Unpin,
+    {
+        WriteAll { writer: self, buf }
+    }
+
+    /// Flushes all the intermediately buffered bytes to their destination
+    ///
+    /// ## Equivalent to:
+    ///
+    /// ```rust,ignore
+    /// async fn flush(&mut self) -> Result<()>
+    /// ```
+    fn flush(&mut self) -> Flush<'_, Self>
+    where
+        Self: Unpin,
+    {
+        Flush { writer: self }
+    }
+
+    /// Flushes and closes the sink
+    ///
+    /// ## Equivalent to:
+    ///
+    /// ```rust,ignore
+    /// async fn close(&mut self) -> Result<()>
+    /// ```
+    fn close(&mut self) -> Close<'_, Self>
+    where
+        Self: Unpin,
+    {
+        Close { writer: self }
+    }
+}
+
+impl<W: Write + ?Sized> WriteExt for W {}
+
+/// Future returned by [`ReadExt::read_exact`]
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct ReadExact<'a, R: ?Sized> {
+    reader: &'a mut R,
+    buf: &'a mut [u8],
+}
+
+impl<R: Read + Unpin + ?Sized> Future for ReadExact<'_, R> {
+    type Output = Result<()>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+
+        while !this.buf.is_empty() {
+            let read = ready!(Pin::new(&mut *this.reader).poll_read(cx, this.buf))?;
+
+            if read == 0 {
+                return Poll::Ready(Err(Error::UnexpectedEof));
+            }
+
+            this.buf = &mut mem::take(&mut this.buf)[read..];
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Future returned by [`WriteExt::write_all`]
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct WriteAll<'a, W: ?Sized> {
+    writer: &'a mut W,
+    buf: &'a [u8],
+}
+
+impl<W: Write + Unpin + ?Sized> Future for WriteAll<'_, W> {
+    type Output = Result<()>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+
+        while !this.buf.is_empty() {
+            let written = ready!(Pin::new(&mut *this.writer).poll_write(cx, this.buf))?;
+
+            if written == 0 {
+                return Poll::Ready(Err(Error::WriteZero));
+            }
+
+            this.buf = &this.buf[written..];
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Future returned by [`WriteExt::flush`]
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Flush<'a, W: ?Sized> {
+    writer: &'a mut W,
+}
+
+impl<W: Write + Unpin + ?Sized> Future for Flush<'_, W> {
+    type Output = Result<()>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut *self.writer).poll_flush(cx)
+    }
+}
+
+/// Future returned by [`WriteExt::close`]
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Close<'a, W: ?Sized> {
+    writer: &'a mut W,
+}
+
+impl<W: Write + Unpin + ?Sized> Future for Close<'_, W> {
+    type Output = Result<()>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut *self.writer).poll_close(cx)
+    }
+}
+
+/// Drives a future which only performs in-memory IO (reading from `&[u8]` or writing to `Vec<u8>`) to completion
+///
+/// # Panics
+///
+/// Panics if the future returns `Poll::Pending` (i.e., it waits on something other than in-memory IO).
+pub(crate) fn poll_in_memory<F: Future>(future: F) -> F::Output {
+    let mut future = core::pin::pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+
+    match future.as_mut().poll(&mut cx) {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("In-memory IO is not expected to return `Poll::Pending`"),
+    }
+}
+
+/// Splits first `len` bytes from `bytes` (returns [`Error::UnexpectedEof`] if there are not enough bytes)
+pub(crate) fn take_bytes<'a>(bytes: &mut &'a [u8], len: usize) -> Result<&'a [u8]> {
+    if bytes.len() < len {
+        return Err(Error::UnexpectedEof);
+    }
+
+    let (head, tail) = bytes.split_at(len);
+    *bytes = tail;
+
+    Ok(head)
+}
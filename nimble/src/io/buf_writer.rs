@@ -0,0 +1,116 @@
+use alloc::vec::Vec;
+use core::{
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+
+use crate::{io::Write, Error, Result};
+
+/// Default capacity of [`BufWriter`](struct.BufWriter.html)
+const DEFAULT_CAPACITY: usize = 8 * 1024;
+
+/// Adds buffering to a [`Write`](trait.Write.html) type
+///
+/// Small writes are collected in an internal buffer and written to the inner writer in large chunks. Writes larger than
+/// the capacity of buffer bypass the buffer. Buffered bytes are written to inner writer when buffer is full or when
+/// writer is flushed or closed.
+#[derive(Debug)]
+pub struct BufWriter<W> {
+    inner: W,
+    buf: Vec<u8>,
+    written: usize,
+}
+
+impl<W> BufWriter<W> {
+    /// Creates a new `BufWriter` with default capacity (8 KiB)
+    #[inline]
+    pub fn new(inner: W) -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY, inner)
+    }
+
+    /// Creates a new `BufWriter` with given buffer capacity
+    #[inline]
+    pub fn with_capacity(capacity: usize, inner: W) -> Self {
+        Self {
+            inner,
+            buf: Vec::with_capacity(capacity),
+            written: 0,
+        }
+    }
+
+    /// Returns a reference to the inner writer
+    #[inline]
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner writer
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Returns buffered bytes which are not yet written to inner writer
+    #[inline]
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf[self.written..]
+    }
+
+    /// Consumes the `BufWriter` and returns the inner writer (buffered bytes which are not yet written are discarded)
+    #[inline]
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write + Unpin> BufWriter<W> {
+    /// Writes all the buffered bytes to inner writer (without flushing inner writer)
+    pub(crate) fn poll_flush_buf(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        while self.written < self.buf.len() {
+            let written =
+                ready!(Pin::new(&mut self.inner).poll_write(cx, &self.buf[self.written..]))?;
+
+            if written == 0 {
+                return Poll::Ready(Err(Error::WriteZero));
+            }
+
+            self.written += written;
+        }
+
+        self.buf.clear();
+        self.written = 0;
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<W: Write + Unpin> Write for BufWriter<W> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<Result<usize>> {
+        let this = self.get_mut();
+
+        if this.buf.len() + buf.len() > this.buf.capacity() {
+            ready!(this.poll_flush_buf(cx))?;
+        }
+
+        if buf.len() >= this.buf.capacity() {
+            Pin::new(&mut this.inner).poll_write(cx, buf)
+        } else {
+            this.buf.extend_from_slice(buf);
+            Poll::Ready(Ok(buf.len()))
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let this = self.get_mut();
+
+        ready!(this.poll_flush_buf(cx))?;
+        Pin::new(&mut this.inner).poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let this = self.get_mut();
+
+        ready!(this.poll_flush_buf(cx))?;
+        Pin::new(&mut this.inner).poll_close(cx)
+    }
+}
@@ -0,0 +1,74 @@
+use core::{
+    cmp,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::BufMut;
+
+use crate::{io::Write, Result};
+
+/// Adapter implementing [`Write`](trait.Write.html) for `bytes`' `BufMut` types
+///
+/// Bytes are written directly into the remaining capacity of `BufMut`. When `BufMut` cannot grow any further (for
+/// example, `&mut [u8]`), writes return `0` (which results in a [`WriteZero`](../enum.Error.html#variant.WriteZero)
+/// error when writing all the bytes).
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BufMutWriter<B>(B);
+
+impl<B> BufMutWriter<B> {
+    /// Wraps a `BufMut` type
+    #[inline]
+    pub const fn new(inner: B) -> Self {
+        Self(inner)
+    }
+
+    /// Returns a reference to the inner `BufMut` type
+    #[inline]
+    pub fn get_ref(&self) -> &B {
+        &self.0
+    }
+
+    /// Returns a mutable reference to the inner `BufMut` type
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.0
+    }
+
+    /// Consumes the adapter and returns the inner `BufMut` type
+    #[inline]
+    pub fn into_inner(self) -> B {
+        self.0
+    }
+}
+
+impl<B> From<B> for BufMutWriter<B> {
+    #[inline]
+    fn from(inner: B) -> Self {
+        Self::new(inner)
+    }
+}
+
+impl<B: BufMut + Unpin> Write for BufMutWriter<B> {
+    #[inline]
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize>> {
+        let len = cmp::min(buf.len(), self.0.remaining_mut());
+        self.0.put_slice(&buf[..len]);
+
+        Poll::Ready(Ok(len))
+    }
+
+    #[inline]
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    #[inline]
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
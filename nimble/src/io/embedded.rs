@@ -0,0 +1,86 @@
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use embedded_io::{Read as EmbeddedRead, Write as EmbeddedWrite};
+
+use crate::{
+    io::{Read, Write},
+    Error, Result,
+};
+
+/// Adapter implementing [`Read`](trait.Read.html) and [`Write`](trait.Write.html) for `embedded-io`'s blocking `Read`
+/// and `Write` types (its futures never return `Poll::Pending`)
+#[derive(Debug, Default, Clone, Copy)]
+pub struct EmbeddedIo<T>(T);
+
+impl<T> EmbeddedIo<T> {
+    /// Wraps an `embedded-io` IO type
+    #[inline]
+    pub const fn new(inner: T) -> Self {
+        Self(inner)
+    }
+
+    /// Returns a reference to the inner IO type
+    #[inline]
+    pub fn get_ref(&self) -> &T {
+        &self.0
+    }
+
+    /// Returns a mutable reference to the inner IO type
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+
+    /// Consumes the adapter and returns the inner IO type
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for EmbeddedIo<T> {
+    #[inline]
+    fn from(inner: T) -> Self {
+        Self::new(inner)
+    }
+}
+
+impl<T: EmbeddedRead + Unpin> Read for EmbeddedIo<T> {
+    #[inline]
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<Result<usize>> {
+        Poll::Ready(self.0.read(buf).map_err(into_error))
+    }
+}
+
+impl<T: EmbeddedWrite + Unpin> Write for EmbeddedIo<T> {
+    #[inline]
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize>> {
+        Poll::Ready(self.0.write(buf).map_err(into_error))
+    }
+
+    #[inline]
+    fn poll_flush(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Poll::Ready(self.0.flush().map_err(into_error))
+    }
+
+    #[inline]
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.poll_flush(cx)
+    }
+}
+
+#[inline]
+pub(super) fn into_error<E: embedded_io::Error>(err: E) -> Error {
+    Error::EmbeddedIoError(err.kind())
+}
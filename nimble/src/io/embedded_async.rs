@@ -0,0 +1,128 @@
+use alloc::vec::Vec;
+use core::cmp;
+
+use embedded_io_async::{Read as EmbeddedRead, Write as EmbeddedWrite};
+
+use crate::{
+    io::embedded::into_error, Config, Decode, Decoder, Encode, Error, Progress, Result,
+    DEFAULT_MAX_VALUE_SIZE,
+};
+
+/// Size of the chunks read from inner reader while decoding a value
+const CHUNK_SIZE: usize = 256;
+
+/// Adapter for encoding values to `embedded-io-async`'s `Write` types and decoding values from its `Read` types
+///
+/// Futures of `embedded-io-async` borrow the IO type, so they can not be driven by [`Read`](trait.Read.html) and
+/// [`Write`](trait.Write.html). Instead, values are encoded in memory and written with [`encode`](#method.encode), and
+/// decoded with [`decode`](#method.decode) by feeding chunks read from inner reader to a
+/// [`Decoder`](../struct.Decoder.html). No more bytes than the decoder asks for are read, so bytes after the end of a
+/// decoded value (or after an invalid byte) are left in inner reader for the next value.
+#[derive(Debug)]
+pub struct EmbeddedAsyncIo<T> {
+    inner: T,
+    max_size: usize,
+}
+
+impl<T> EmbeddedAsyncIo<T> {
+    /// Wraps an `embedded-io-async` IO type
+    #[inline]
+    pub const fn new(inner: T) -> Self {
+        Self {
+            inner,
+            max_size: DEFAULT_MAX_VALUE_SIZE,
+        }
+    }
+
+    /// Sets maximum size of a decoded value (defaults to
+    /// [`DEFAULT_MAX_VALUE_SIZE`](../constant.DEFAULT_MAX_VALUE_SIZE.html))
+    #[inline]
+    pub fn with_max_size(mut self, max_size: usize) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Returns a reference to the inner IO type
+    #[inline]
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner IO type
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Consumes the adapter and returns the inner IO type
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T> From<T> for EmbeddedAsyncIo<T> {
+    #[inline]
+    fn from(inner: T) -> Self {
+        Self::new(inner)
+    }
+}
+
+impl<T: EmbeddedWrite> EmbeddedAsyncIo<T> {
+    /// Writes encoded byte array of a value to inner writer and returns the number of bytes written
+    ///
+    /// Writer is flushed at the end if [`Config::flush`](../struct.Config.html#method.flush) is `true`.
+    pub async fn encode<E: Encode + ?Sized>(
+        &mut self,
+        config: &Config,
+        value: &E,
+    ) -> Result<usize> {
+        let mut bytes = Vec::with_capacity(value.size());
+        let written = value.encode_to_vec(config, &mut bytes);
+
+        let mut remaining = &bytes[..];
+
+        while !remaining.is_empty() {
+            match self.inner.write(remaining).await.map_err(into_error)? {
+                0 => return Err(Error::WriteZero),
+                n => remaining = &remaining[n..],
+            }
+        }
+
+        if config.flush() {
+            self.inner.flush().await.map_err(into_error)?;
+        }
+
+        Ok(written)
+    }
+}
+
+impl<T: EmbeddedRead> EmbeddedAsyncIo<T> {
+    /// Decodes a value from inner reader
+    ///
+    /// Returns [`Error::ValueTooLarge`](../enum.Error.html#variant.ValueTooLarge) if the value is larger than maximum
+    /// size.
+    pub async fn decode<D: Decode + 'static>(&mut self, config: &Config) -> Result<D> {
+        let mut decoder = Decoder::with_config(config.clone()).with_max_size(self.max_size);
+        let mut chunk = [0; CHUNK_SIZE];
+        let mut read = 0;
+
+        loop {
+            match decoder.feed(&chunk[..read])? {
+                Progress::Done(value, _) => return Ok(value),
+                Progress::NeedMore(needed) => {
+                    let len = cmp::min(needed, CHUNK_SIZE);
+                    read = self
+                        .inner
+                        .read(&mut chunk[..len])
+                        .await
+                        .map_err(into_error)?;
+
+                    if read == 0 {
+                        return Err(Error::UnexpectedEof);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_util::io::{AsyncRead, AsyncWrite};
+
+use crate::{
+    io::{Read, Write},
+    Result,
+};
+
+/// Adapter implementing [`Read`](trait.Read.html) and [`Write`](trait.Write.html) for `futures`' `AsyncRead` and
+/// `AsyncWrite` types
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FuturesIo<T>(T);
+
+impl<T> FuturesIo<T> {
+    /// Wraps a `futures` IO type
+    #[inline]
+    pub const fn new(inner: T) -> Self {
+        Self(inner)
+    }
+
+    /// Returns a reference to the inner IO type
+    #[inline]
+    pub fn get_ref(&self) -> &T {
+        &self.0
+    }
+
+    /// Returns a mutable reference to the inner IO type
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+
+    /// Consumes the adapter and returns the inner IO type
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for FuturesIo<T> {
+    #[inline]
+    fn from(inner: T) -> Self {
+        Self::new(inner)
+    }
+}
+
+impl<T: AsyncRead + Unpin> Read for FuturesIo<T> {
+    #[inline]
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<Result<usize>> {
+        Pin::new(&mut self.0).poll_read(cx, buf).map_err(Into::into)
+    }
+}
+
+impl<T: AsyncWrite + Unpin> Write for FuturesIo<T> {
+    #[inline]
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize>> {
+        Pin::new(&mut self.0)
+            .poll_write(cx, buf)
+            .map_err(Into::into)
+    }
+
+    #[inline]
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Pin::new(&mut self.0).poll_flush(cx).map_err(Into::into)
+    }
+
+    #[inline]
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Pin::new(&mut self.0).poll_close(cx).map_err(Into::into)
+    }
+}
@@ -0,0 +1,93 @@
+use core::{
+    cmp,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+
+use bytes::Buf;
+use futures_util::stream::Stream;
+
+use crate::{io::Read, Error, Result};
+
+/// Adapter implementing [`Read`](trait.Read.html) for `futures`' `Stream`s of byte chunks (for example,
+/// `Stream<Item = Result<Bytes, E>>`)
+///
+/// Chunks are read one after another as they arrive (without concatenating them), so values straddling chunk boundaries
+/// are decoded transparently. Errors yielded by the stream are converted into [`Error`](../enum.Error.html) and end of
+/// the stream is treated as end of source.
+#[derive(Debug)]
+pub struct StreamReader<S, B> {
+    stream: S,
+    chunk: Option<B>,
+}
+
+impl<S, B> StreamReader<S, B> {
+    /// Wraps a stream of byte chunks
+    #[inline]
+    pub const fn new(stream: S) -> Self {
+        Self {
+            stream,
+            chunk: None,
+        }
+    }
+
+    /// Returns a reference to the inner stream
+    #[inline]
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    /// Returns a mutable reference to the inner stream
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+
+    /// Consumes the adapter and returns the inner stream along with the unread part of the current chunk (if any)
+    #[inline]
+    pub fn into_inner(self) -> (S, Option<B>) {
+        (self.stream, self.chunk)
+    }
+}
+
+impl<S, B> From<S> for StreamReader<S, B> {
+    #[inline]
+    fn from(stream: S) -> Self {
+        Self::new(stream)
+    }
+}
+
+impl<S, B, E> Read for StreamReader<S, B>
+where
+    S: Stream<Item = core::result::Result<B, E>> + Unpin,
+    B: Buf + Unpin,
+    E: Into<Error>,
+{
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<Result<usize>> {
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        loop {
+            if let Some(chunk) = self.chunk.as_mut().filter(|chunk| chunk.has_remaining()) {
+                let len = cmp::min(chunk.remaining(), buf.len());
+                chunk.copy_to_slice(&mut buf[..len]);
+
+                return Poll::Ready(Ok(len));
+            }
+
+            match ready!(Pin::new(&mut self.stream).poll_next(cx)) {
+                Some(Ok(chunk)) => self.chunk = Some(chunk),
+                Some(Err(err)) => return Poll::Ready(Err(err.into())),
+                None => {
+                    self.chunk = None;
+                    return Poll::Ready(Ok(0));
+                }
+            }
+        }
+    }
+}
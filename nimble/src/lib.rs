@@ -1,3 +1,4 @@
+#![cfg_attr(not(feature = "std"), no_std)]
 #![forbid(unsafe_code)]
 #![deny(missing_docs, unstable_features)]
 //! # Nimble
@@ -26,14 +27,7 @@
 //!
 //! ```toml
 //! [dependencies]
-//! nimble = { version = "0.2", features = ["derive"] }
-//! ```
-//!
-//! Or, if you are in an environment based on `tokio`, use:
-//!
-//! ```toml
-//! [dependencies]
-//! nimble = { version = "0.2", default-features = false, features = ["derive", "tokio"] }
+//! nimble = { version = "0.3", features = ["derive"] }
 //! ```
 //!
 //! For encoding and decoding, any type must implement two traits provided by this crate, i.e., `Encode` and `Decode`. For
@@ -50,53 +44,355 @@
 //! ```
 //!
 //! Now you can use `encode()` and `decode()` functions to encode and decode values of `MyStruct`. In addition to this, you
-//! can also use `MyStruct::encode_to()` function to encode values directly to a type implementing `AsyncWrite` and
-//! `MyStruct::decode_from()` function to decode values directly from a type implementing `AsyncRead`.
+//! can also use `MyStruct::encode_to()` function to encode values directly to a type implementing `nimble::io::Write` and
+//! `MyStruct::decode_from()` function to decode values directly from a type implementing `nimble::io::Read`.
+//!
+//! `nimble` defines its own runtime agnostic IO traits. To use `futures`' or `tokio`'s IO types, wrap them in
+//! `nimble::io::FuturesIo` or `nimble::io::TokioIo` respectively:
+//!
+//! ```rust,ignore
+//! use nimble::io::TokioIo;
+//!
+//! let value: MyStruct = nimble::decode_from(TokioIo::new(&mut tcp_stream)).await?;
+//! ```
+//!
+//! `encode_to()` stages encoded bytes in an internal buffer and writes them in large chunks, so encoding a value to an
+//! unbuffered writer (for example, a TCP stream) does not issue a write per field. Use `Config::with_flush()` to also flush
+//! the writer after each encoded value. `nimble::io::BufWriter` adds the same buffering to any `nimble::io::Write` type.
+//!
+//! For synchronous code, `encode_to_writer()` and `decode_from_reader()` functions encode and decode values using blocking
+//! `std::io::Write` and `std::io::Read` types. These use the same binary encoding scheme as their `async` counterparts and
+//! do not need an executor.
+//!
+//! In-memory encoding and decoding (`encode()`, `decode()`, `Encode::encode_to_vec()` and `Decode::decode_from_slice()`)
+//! does not go through `async` machinery at all: `Encode::encode_to_vec()` appends encoded bytes to a `Vec<u8>` and
+//! `Decode::decode_from_slice()` decodes a value from the start of a `&[u8]`, advancing it past the decoded bytes. Derive
+//! macros generate both methods.
 //!
 //! > Note: Most of the functions exposed by this crate are `async` functions and returns `Future` values. So, you'll need
-//! an executor to drive the `Future` returned from these functions. `async-std` and `tokio` are two popular options.
+//! > an executor to drive the `Future` returned from these functions. `async-std` and `tokio` are two popular options.
+//!
+//! ### Zero-copy decoding
+//!
+//! `DecodeBorrowed` trait decodes values which borrow from the input byte slice (`&str`, `&[u8]` and `Cow::Borrowed`)
+//! instead of copying. It can be derived for types with lifetime parameters (when `"derive"` feature is enabled):
+//!
+//! ```rust,ignore
+//! use nimble::{DecodeBorrowed, Encode};
+//!
+//! #[derive(Encode, DecodeBorrowed)]
+//! struct Message<'a> {
+//!     id: u32,
+//!     name: &'a str,
+//!     payload: &'a [u8],
+//! }
+//!
+//! let message: Message<'_> = nimble::decode_borrowed(&bytes)?;
+//! ```
+//!
+//! ### Incremental decoding
+//!
+//! `Decoder` is a push-based (sans-IO) decoder for environments where bytes arrive in chunks of arbitrary sizes and a
+//! reader cannot be awaited (for example, inside an event loop):
+//!
+//! ```rust,ignore
+//! use nimble::{Decoder, Progress};
+//!
+//! let mut decoder = Decoder::<MyStruct>::new().with_max_size(64 * 1024);
+//!
+//! match decoder.feed(&chunk)? {
+//!     Progress::NeedMore(missing) => { /* wait until at least `missing` more bytes arrive */ }
+//!     Progress::Done(value, consumed) => { /* feed `chunk[consumed..]` to decode next value */ }
+//! }
+//! ```
+//!
+//! Decoding of a partially received value is resumed where it stopped when more bytes are fed, so every byte is decoded
+//! only once. Values larger than the maximum size (8 MiB by default) are rejected with `Error::ValueTooLarge`.
+//!
+//! ### Encoding sequences element by element
+//!
+//! `SeqEncoder` writes a sequence in the same format as `Vec<T>` from elements produced one at a time (for example, by an
+//! `Iterator` or a `Stream`) without collecting them first, as long as the number of elements is known in advance:
+//!
+//! ```rust,ignore
+//! use nimble::SeqEncoder;
+//!
+//! let mut encoder = SeqEncoder::<Row, _>::new(rows.len(), &mut writer).await?;
+//! encoder.encode_iter(rows.iter()).await?;
+//! encoder.finish().await?;
+//! ```
+//!
+//! ### Sequences of unknown length
+//!
+//! `Chunked<T>` wraps a `Vec<T>` which is encoded as length-prefixed chunks of elements terminated by an empty chunk.
+//! `ChunkedEncoder` writes elements in this format as they are produced (buffering at most one chunk in memory), so that
+//! sequences whose length is not known in advance (for example, query results) can be streamed:
+//!
+//! ```rust,ignore
+//! use nimble::{Chunked, ChunkedEncoder};
+//!
+//! let mut encoder = ChunkedEncoder::new(&mut writer);
+//!
+//! while let Some(row) = rows.next().await {
+//!     encoder.encode(&row?).await?;
+//! }
+//!
+//! encoder.finish().await?;
+//!
+//! let rows: Chunked<Row> = nimble::decode_from(&mut reader).await?;
+//! ```
+//!
+//! `nimble::stream::decode_chunked_stream()` decodes the elements one at a time as a `Stream`.
+//!
+//! ### Streaming large byte payloads
+//!
+//! `Blob` streams a byte payload of known length (encoded in the same format as `Vec<u8>`) from a reader instead of holding
+//! it in memory. `Blob` does not implement `Encode` and `Decode`: since the payload cannot be followed by other fields
+//! without reading it first, a blob is written (and read) after encoding (or decoding) the other fields of a message:
+//!
+//! ```rust,ignore
+//! use nimble::{io::FuturesIo, Blob};
+//!
+//! nimble::encode_to(&header, &mut writer).await?;
+//! Blob::new(file_len, FuturesIo::new(file)).encode_to(&config, &mut writer).await?;
+//!
+//! let header: Header = nimble::decode_from(&mut reader).await?;
+//! let blob = Blob::decode_from(&config, &mut reader).await?;
+//! futures::io::copy(blob.into_inner(), &mut output).await?;
+//! ```
+//!
+//! ### Maximum encoded size
+//!
+//! `MaxSize` trait provides a compile-time upper bound on the size of encoded values of bounded types (primitives, fixed
+//! arrays, tuples, `Option`, etc.), which can be used to size fixed buffers. It can be derived for structs and enums whose
+//! fields implement `MaxSize` (when `"derive"` feature is enabled):
+//!
+//! ```rust,ignore
+//! use nimble::{Encode, MaxSize};
+//!
+//! #[derive(Encode, MaxSize)]
+//! struct Header {
+//!     id: u32,
+//!     flags: Option<u8>,
+//! }
+//!
+//! let mut buf = [0u8; Header::MAX_SIZE];
+//! let written = nimble::encode_into_slice(&header, &mut buf)?;
+//! ```
+//!
+//! ### Framed messages
+//!
+//! `frame` module defines a self-describing frame format (magic bytes, format version, application defined flags and
+//! payload length followed by encoded value) for exchanging messages between independent services:
+//!
+//! ```rust,ignore
+//! use nimble::{frame, Config};
+//!
+//! let config = Config::default();
+//!
+//! frame::write_frame(&config, 0, &message, &mut writer).await?;
+//! let frame: frame::Frame<Message> = frame::read_frame(&config, frame::DEFAULT_MAX_PAYLOAD_SIZE, &mut reader).await?;
+//! ```
+//!
+//! ### Implementing `Encode` and `Decode` manually
+//!
+//! `Encode::encode_to()` and `Decode::decode_from()` return `Send` futures and can be implemented using `async fn` syntax
+//! (no boxing of futures is involved):
+//!
+//! ```rust,ignore
+//! use nimble::{io::Write, Config, Encode, Result};
+//!
+//! struct MyType(u32);
+//!
+//! impl Encode for MyType {
+//!     fn size(&self) -> usize {
+//!         self.0.size()
+//!     }
+//!
+//!     async fn encode_to<W>(&self, config: &Config, writer: W) -> Result<usize>
+//!     where
+//!         W: Write + Unpin + Send,
+//!     {
+//!         self.0.encode_to(config, writer).await
+//!     }
+//! }
+//! ```
+//!
+//! `Encode::encode_to_vec()` and `Decode::decode_from_slice()` have default implementations which drive `async` methods
+//! to completion, so overriding them is optional (but recommended for faster in-memory encoding/decoding).
+//!
+//! > Note: Recursive types should use `Box` or `Arc` for indirection (for example, `Vec<Box<Node>>` instead of
+//! > `Vec<Node>`) because futures of values behind these smart pointers are boxed to keep size of outer future finite.
+//!
+//! #### Migrating from `nimble` `0.2`
+//!
+//! Previous versions used `#[nimble::async_trait]` for implementing `Encode` and `Decode`. To migrate hand-written
+//! implementations, remove `#[nimble::async_trait]` attribute from `impl` blocks and keep the `async fn` bodies as they
+//! are. Code generated by derive macros does not need any change.
+//!
+//! `Config` can no longer be built with a struct literal. Use `Config::default().with_endianness(Endianness::BigEndian)`
+//! instead of `Config { endianness: Endianness::BigEndian, ..Config::default() }`.
+//!
+//! ### Single-threaded mode
+//!
+//! By default, futures returned by `Encode` and `Decode` are `Send` and `Encode` implementations require inner values to be
+//! `Sync`. For types which are not `Send`/`Sync` (for example, `Rc<RefCell<..>>` based types), `LocalEncode` and
+//! `LocalDecode` traits provide single-threaded counterparts whose futures are not required to be `Send`, so that these
+//! types can be encoded/decoded on a single-threaded executor (for example, `tokio`'s `LocalSet`). Both traits are
+//! implemented for the same types as `Encode` and `Decode` along with `Rc` and `RefCell`, produce the same encoding, and
+//! can be derived using `LocalEncode` and `LocalDecode` derive macros:
+//!
+//! ```rust
+//! # #[cfg(feature = "derive")] {
+//! use std::{cell::RefCell, rc::Rc};
+//!
+//! use nimble::{LocalDecode, LocalEncode};
+//!
+//! #[derive(LocalEncode, LocalDecode)]
+//! struct Node {
+//!     value: u32,
+//!     children: Vec<Rc<RefCell<Node>>>,
+//! }
+//!
+//! let node = Node { value: 1, children: vec![] };
+//!
+//! let mut encoded = Vec::new();
+//! node.encode_to_vec_local(nimble::config(), &mut encoded);
+//! let decoded = Node::decode_from_slice_local(nimble::config(), &mut &encoded[..]).unwrap();
+//!
+//! assert_eq!(1, decoded.value);
+//! # }
+//! ```
 //!
 //! ### Features
 //!
-//! - `futures`: Select this feature when you want to implement `Encode` and `Decode` using `futures`'
-//!   `AsyncRead`/`AsyncWrite` traits.
+//! - `std`: Enables `Encode` and `Decode` implementations for `std` only types (`HashMap`, `HashSet`), conversion of
+//!   `std::io::Error` and blocking `encode_to_writer()`/`decode_from_reader()` functions. Without this feature, `nimble`
+//!   is a `no_std` crate which depends on `alloc`.
 //!   - **Enabled** by default.
-//! - `tokio`: Select this feature when you want to implement `Encode` and `Decode` using `tokio`'s `AsyncRead`/`AsyncWrite`
-//!   traits.
+//! - `futures`: Enables `nimble::io::FuturesIo` adapter for using `futures`' `AsyncRead`/`AsyncWrite` types with
+//!   `Encode` and `Decode`, `nimble::stream` module for decoding a sequence of values (or elements of an encoded
+//!   sequence) as a `Stream` and `nimble::Sink` for writing encoded values as a `Sink`.
+//!   - **Enabled** by default.
+//! - `tokio`: Enables `nimble::io::TokioIo` adapter for using `tokio`'s `AsyncRead`/`AsyncWrite` types with `Encode` and
+//!   `Decode`.
+//!   - **Disabled** by default.
+//! - `embedded-io`: Enables `nimble::io::EmbeddedIo` adapter for using `embedded-io`'s blocking `Read`/`Write` types with
+//!   `Encode` and `Decode`. Works in `no_std` environments.
+//!   - **Disabled** by default.
+//! - `embedded-io-async`: Enables `nimble::io::EmbeddedAsyncIo` adapter for encoding values to and decoding values from
+//!   `embedded-io-async`'s `Read`/`Write` types. Enables `embedded-io` feature and works in `no_std` environments.
+//!   - **Disabled** by default.
+//! - `bytes`: Enables `Encode` and `Decode` implementations for `bytes`' `Bytes` and `BytesMut`, encoding into `BufMut`
+//!   types (`encode_to_buf()`) and zero-copy decoding from `Bytes` (`decode_bytes()`), where `Bytes` values are decoded
+//!   as slices of input without copying. Works in `no_std` environments. Along with `futures` feature, also enables
+//!   `nimble::io::StreamReader` adapter for decoding values from a `Stream` of byte chunks (for example, HTTP bodies).
 //!   - **Disabled** by default.
-//! - `derive`: Enables derive macros for implementing `Encode` and `Decode` traits.
+//! - `codec`: Enables `nimble::codec::NimbleCodec` for using `Encode`/`Decode` types with `tokio-util`'s `Framed`,
+//!   `FramedRead` and `FramedWrite` (values are sent in length delimited frames with a configurable maximum frame size).
+//!   Enables `bytes` feature.
+//!   - **Disabled** by default.
+//! - `derive`: Enables derive macros for implementing `Encode`, `Decode`, `LocalEncode`, `LocalDecode`,
+//!   `DecodeBorrowed` and `MaxSize` traits.
 //!   - **Disabled** by default.
 //!
-//! > Note: Features `futures` and `tokio` are mutually exclusive, i.e., only one of them can be enabled at a time.
-//! > Compilation will fail if either both of them are enabled or none of them are enabled.
-#[cfg(all(feature = "futures", feature = "tokio"))]
-compile_error!("Features `futures` and `tokio` are mutually exclusive");
-
-#[cfg(not(any(feature = "futures", feature = "tokio")))]
-compile_error!("Either feature `futures` or `tokio` must be enabled for this crate");
+//! > Note: Features `futures`, `tokio`, `embedded-io` and `embedded-io-async` can be enabled at the same time. `futures`
+//! > and `tokio` features enable `std` feature.
+//!
+//! ### `no_std` support
+//!
+//! Disable default features to use `nimble` in `no_std` environments with an allocator:
+//!
+//! ```toml
+//! [dependencies]
+//! nimble = { version = "0.3", default-features = false, features = ["derive", "embedded-io-async"] }
+//! ```
+//!
+//! With `embedded-io-async` feature, values can be exchanged over `embedded-io-async` IO types (for example, a UART of
+//! an async HAL) using `nimble::io::EmbeddedAsyncIo`.
+extern crate alloc;
 
+mod blob;
+#[cfg(feature = "std")]
+mod blocking;
+mod chunked;
+#[cfg(feature = "codec")]
+pub mod codec;
 mod config;
 mod decode;
+mod decode_borrowed;
+mod decoder;
 mod encode;
 mod error;
+pub mod frame;
+mod local;
+mod marker;
+mod max_size;
+mod seq_encoder;
+#[cfg(feature = "futures")]
+mod sink;
 mod varint;
 
 pub mod io;
+#[cfg(feature = "futures")]
+pub mod stream;
 
 #[cfg(feature = "derive")]
-pub use nimble_derive::{Decode, Encode};
+pub use nimble_derive::{Decode, DecodeBorrowed, Encode, LocalDecode, LocalEncode, MaxSize};
 
-/// Utility macro for implementing [`Encode`](trait.Encode.html) and [`Decode`](trait.Decode.html) traits.
-pub use async_trait::async_trait;
+#[doc(hidden)]
+/// Items used by code generated from derive macros (not a public API)
+pub mod __private {
+    pub use alloc::vec::Vec;
+
+    pub use crate::{
+        decode::{DecodeFieldInPlace, DecodeFieldReplace, FieldProbe},
+        encode::sum_fixed_sizes,
+        max_size::max,
+        varint::encoded_len as varint_len,
+    };
+
+    #[cfg(feature = "bytes")]
+    pub use bytes::Bytes;
+}
 
+#[cfg(feature = "bytes")]
+#[doc(hidden)]
+#[macro_export]
+/// Expands to given items only when `bytes` feature is enabled (used by code generated from derive macros)
+macro_rules! __cfg_bytes {
+    ($($item: tt)*) => {
+        $($item)*
+    };
+}
+
+#[cfg(not(feature = "bytes"))]
+#[doc(hidden)]
+#[macro_export]
+/// Expands to given items only when `bytes` feature is enabled (used by code generated from derive macros)
+macro_rules! __cfg_bytes {
+    ($($item: tt)*) => {};
+}
+
+#[cfg(feature = "futures")]
+pub use self::sink::Sink;
 pub use self::{
+    blob::{Blob, BlobReader},
+    chunked::{Chunked, ChunkedEncoder},
     config::{Config, Endianness},
     decode::Decode,
+    decode_borrowed::DecodeBorrowed,
+    decoder::{Decoder, Progress, DEFAULT_MAX_VALUE_SIZE},
     encode::Encode,
     error::{Error, Result},
+    local::{LocalDecode, LocalEncode},
+    max_size::MaxSize,
+    seq_encoder::SeqEncoder,
     varint::VarInt,
 };
 
+use alloc::vec::Vec;
+
 use self::io::{Read, Write};
 
 const DEFAULT_CONFIG: Config = Config::new_default();
@@ -112,7 +408,9 @@ pub async fn encode<E: Encode + ?Sized>(value: &E) -> Vec<u8> {
     DEFAULT_CONFIG.encode(value).await
 }
 
-/// Writes encoded byte array to writer and returns the number of bytes written
+/// Writes encoded byte array to writer using default configuration and returns the number of bytes written
+///
+/// Encoded bytes are staged in an internal buffer and written to writer in large chunks (see [`Config::encode_to`]).
 #[inline]
 pub async fn encode_to<E: Encode + ?Sized, W: Write + Unpin + Send>(
     value: &E,
@@ -121,20 +419,68 @@ pub async fn encode_to<E: Encode + ?Sized, W: Write + Unpin + Send>(
     DEFAULT_CONFIG.encode_to(value, writer).await
 }
 
+/// Writes encoded byte array into the start of `buf` (without allocating) using default configuration and returns the
+/// number of bytes written
+#[inline]
+pub fn encode_into_slice<E: Encode + ?Sized>(value: &E, buf: &mut [u8]) -> Result<usize> {
+    DEFAULT_CONFIG.encode_into_slice(value, buf)
+}
+
 /// Decodes a value from bytes using default configuration
 #[inline]
 pub async fn decode<D: Decode, T: AsRef<[u8]>>(bytes: T) -> Result<D> {
     DEFAULT_CONFIG.decode(bytes).await
 }
 
+#[cfg(feature = "bytes")]
+/// Writes encoded byte array into a `BufMut` using default configuration and returns the number of bytes written
+#[inline]
+pub fn encode_to_buf<E: Encode + ?Sized, B: bytes::BufMut + Send + ?Sized>(
+    value: &E,
+    buf: &mut B,
+) -> Result<usize> {
+    DEFAULT_CONFIG.encode_to_buf(value, buf)
+}
+
+#[cfg(feature = "bytes")]
+/// Decodes a value from `Bytes` using default configuration (`Bytes` values in decoded value are zero-copy slices of
+/// `bytes`)
+#[inline]
+pub fn decode_bytes<D: Decode>(bytes: bytes::Bytes) -> Result<D> {
+    DEFAULT_CONFIG.decode_bytes(bytes)
+}
+
+/// Decodes a value which can borrow from `bytes` using default configuration
+#[inline]
+pub fn decode_borrowed<'de, D: DecodeBorrowed<'de>>(bytes: &'de [u8]) -> Result<D> {
+    DEFAULT_CONFIG.decode_borrowed(bytes)
+}
+
 /// Decodes values from reader
 #[inline]
 pub async fn decode_from<D: Decode, R: Read + Unpin + Send>(reader: R) -> Result<D> {
     DEFAULT_CONFIG.decode_from(reader).await
 }
 
-#[cfg(test)]
-#[cfg(not(feature = "tokio"))]
+#[cfg(feature = "std")]
+/// Writes encoded byte array to a blocking `std::io::Write` using default configuration and returns the number of bytes
+/// written
+#[inline]
+pub fn encode_to_writer<E: Encode + ?Sized, W: std::io::Write + Send>(
+    value: &E,
+    writer: W,
+) -> Result<usize> {
+    DEFAULT_CONFIG.encode_to_writer(value, writer)
+}
+
+#[cfg(feature = "std")]
+/// Decodes values from a blocking `std::io::Read` using default configuration
+#[inline]
+pub fn decode_from_reader<D: Decode, R: std::io::Read + Send>(reader: R) -> Result<D> {
+    DEFAULT_CONFIG.decode_from_reader(reader)
+}
+
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use core::{
         convert::{TryFrom, TryInto},
@@ -148,7 +494,10 @@ mod tests {
     use futures_executor as executor;
     use rand::random;
 
-    use crate::{decode, encode, Encode, VarInt};
+    use crate::{
+        decode, decode_from, decode_from_reader, encode, encode_to_writer, Decode, Encode, Error,
+        VarInt,
+    };
 
     macro_rules! primitive_test {
         ($type: ty, $name: ident) => {
@@ -322,6 +671,436 @@ mod tests {
         })
     }
 
+    #[test]
+    fn blocking_test() {
+        let original = vec!["hello".to_string(), "world".to_string()];
+
+        let mut encoded = Vec::new();
+        let written = encode_to_writer(&original, &mut encoded).unwrap();
+        assert_eq!(original.size(), written);
+        assert_eq!(executor::block_on(encode(&original)), encoded);
+
+        let decoded: Vec<String> = decode_from_reader(&encoded[..]).unwrap();
+        assert_eq!(original, decoded, "Invalid encoding/decoding");
+    }
+
+    #[test]
+    fn sync_test() {
+        let config = crate::config();
+        let original = (Some(vec!["hello".to_string()]), 'a', VarInt::from(300u16));
+
+        let mut encoded = Vec::new();
+        let written = original.encode_to_vec(config, &mut encoded);
+        assert_eq!(original.size(), written);
+        assert_eq!(executor::block_on(encode(&original)), encoded);
+
+        encoded.push(42);
+        let mut bytes = &encoded[..];
+        let decoded =
+            <(Option<Vec<String>>, char, VarInt)>::decode_from_slice(config, &mut bytes).unwrap();
+        assert_eq!(original, decoded, "Invalid encoding/decoding");
+        assert_eq!(bytes, &[42]);
+
+        let mut bytes = &encoded[..3];
+        assert!(matches!(
+            <(Option<Vec<String>>, char, VarInt)>::decode_from_slice(config, &mut bytes),
+            Err(crate::Error::UnexpectedEof)
+        ));
+    }
+
+    #[test]
+    fn encode_into_slice_test() {
+        let original = (Some(vec!["hello".to_string()]), 'a', VarInt::from(300u16));
+        let encoded = executor::block_on(encode(&original));
+
+        let mut buf = [0u8; 64];
+        let written = crate::encode_into_slice(&original, &mut buf).unwrap();
+        assert_eq!(original.size(), written);
+        assert_eq!(encoded, &buf[..written]);
+
+        let mut buf = [0u8; 4];
+        assert!(matches!(
+            crate::encode_into_slice(&original, &mut buf),
+            Err(crate::Error::BufferTooSmall { needed }) if needed == original.size()
+        ));
+        assert_eq!(buf, [0u8; 4]);
+    }
+
+    #[test]
+    fn max_size_test() {
+        use crate::MaxSize;
+
+        assert_eq!(8, u64::MAX_SIZE);
+        assert_eq!(4, char::MAX_SIZE);
+        assert_eq!(19, VarInt::MAX_SIZE);
+        assert_eq!(19, VarInt::from(u128::MAX).size());
+        assert_eq!(3, <Option<u16>>::MAX_SIZE);
+        assert_eq!(9, <Result<u8, u64>>::MAX_SIZE);
+        assert_eq!(64, <[u32; 16]>::MAX_SIZE);
+        assert_eq!(
+            7,
+            <(u8, Box<u16>, core::marker::PhantomData<String>, u32)>::MAX_SIZE
+        );
+    }
+
+    #[test]
+    fn fixed_size_test() {
+        assert_eq!(Some(8), u64::FIXED_SIZE);
+        assert_eq!(Some(2), NonZeroU16::FIXED_SIZE);
+        assert_eq!(Some(8), <[u16; 4]>::FIXED_SIZE);
+        assert_eq!(Some(7), <(u8, Box<u16>, (), u32)>::FIXED_SIZE);
+        assert_eq!(None, <(u8, String)>::FIXED_SIZE);
+        assert_eq!(None, <Option<u8>>::FIXED_SIZE);
+        assert_eq!(None, VarInt::FIXED_SIZE);
+
+        let original = vec![[1u32; 3]; 1000];
+        assert_eq!(2 + 12 * 1000, original.size());
+        assert_eq!(executor::block_on(encode(&original)).len(), original.size());
+
+        let original: BTreeMap<u16, (u8, char)> = (0..300).map(|i| (i, (1, 'a'))).collect();
+        assert_eq!(2 + 7 * 300, original.size());
+        assert_eq!(executor::block_on(encode(&original)).len(), original.size());
+    }
+
+    #[test]
+    fn send_future_test() {
+        fn assert_send<T: Send>(value: T) -> T {
+            value
+        }
+
+        let original = (vec![Box::new(1u32)], Some("hello".to_string()));
+        let mut encoded = Vec::new();
+        let written = executor::block_on(assert_send(
+            original.encode_to(crate::config(), &mut encoded),
+        ))
+        .unwrap();
+        assert_eq!(original.size(), written);
+
+        let decoded = executor::block_on(assert_send(crate::decode_from(&encoded[..]))).unwrap();
+        assert_eq!(original, decoded);
+    }
+
+    #[test]
+    fn decoder_test() {
+        use crate::{Decoder, Progress};
+
+        let first = (
+            vec!["hello".to_string(), "world".to_string()],
+            Some(VarInt::from(300u16)),
+        );
+        let second = (vec![], None);
+
+        let mut encoded = executor::block_on(encode(&first));
+        encoded.extend(executor::block_on(encode(&second)));
+
+        // Feed one byte at a time
+        let mut decoder = Decoder::<(Vec<String>, Option<VarInt>)>::new();
+        let mut decoded = Vec::new();
+
+        for byte in encoded.chunks(1) {
+            match decoder.feed(byte).unwrap() {
+                Progress::NeedMore(hint) => assert!(hint > 0),
+                Progress::Done(value, consumed) => {
+                    assert_eq!(1, consumed);
+                    decoded.push(value);
+                }
+            }
+        }
+
+        assert_eq!(vec![first.clone(), second.clone()], decoded);
+        assert!(!decoder.is_decoding());
+
+        // Feed both values at once
+        let mut chunk = &encoded[..];
+        let mut decoded = Vec::new();
+
+        while !chunk.is_empty() {
+            match decoder.feed(chunk).unwrap() {
+                Progress::NeedMore(_) => unreachable!(),
+                Progress::Done(value, consumed) => {
+                    decoded.push(value);
+                    chunk = &chunk[consumed..];
+                }
+            }
+        }
+
+        assert_eq!(vec![first, second], decoded);
+
+        // Value split in two chunks
+        let mut decoder = Decoder::<(u32, u64)>::new();
+        assert!(matches!(
+            decoder.feed(&[1, 0, 0]),
+            Ok(Progress::NeedMore(_))
+        ));
+        assert!(decoder.is_decoding());
+        assert_eq!(
+            Progress::Done((1, 2), 9),
+            decoder.feed(&[0, 2, 0, 0, 0, 0, 0, 0, 0, 42]).unwrap()
+        );
+
+        // Errors reset decoder
+        let mut decoder = Decoder::<Option<u8>>::new();
+        assert!(matches!(
+            decoder.feed(&[2, 1]),
+            Err(crate::Error::InvalidEnumVariant(_))
+        ));
+        assert!(!decoder.is_decoding());
+    }
+
+    #[test]
+    fn decoder_need_more_test() {
+        use crate::{Decoder, Error, Progress};
+
+        // Exact number of missing bytes is reported
+        let mut decoder = Decoder::<(u32, String)>::new();
+        assert_eq!(Progress::NeedMore(1), decoder.feed(&[1, 0, 0]).unwrap());
+        assert_eq!(Progress::NeedMore(1), decoder.feed(&[0]).unwrap());
+        assert_eq!(Progress::NeedMore(5), decoder.feed(&[5]).unwrap());
+        assert_eq!(Progress::NeedMore(2), decoder.feed(b"hel").unwrap());
+        assert_eq!(
+            Progress::Done((1, "hello".to_string()), 2),
+            decoder.feed(b"lo").unwrap()
+        );
+
+        // Forged length prefix does not allocate and is rejected once it exceeds maximum value size
+        let mut decoder = Decoder::<Vec<String>>::new().with_max_size(1024);
+        assert_eq!(1024, decoder.max_size());
+        assert_eq!(
+            Progress::NeedMore(1),
+            decoder
+                .feed(&[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x0f])
+                .unwrap()
+        );
+        assert!(matches!(
+            decoder.feed(&[0; 2048]),
+            Err(Error::ValueTooLarge { max: 1024, .. })
+        ));
+        assert!(!decoder.is_decoding());
+
+        let mut decoder = Decoder::<Vec<u8>>::new().with_max_size(1024);
+        assert!(matches!(
+            decoder.feed(&[0xff, 0xff, 0x03]),
+            Err(Error::ValueTooLarge { max: 1024, .. })
+        ));
+        assert!(!decoder.is_decoding());
+    }
+
+    #[test]
+    fn decoder_resume_test() {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        use crate::{io::Read, Config, Decoder, Progress, Result};
+
+        static DECODED: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(Debug, PartialEq)]
+        struct Counted(u8);
+
+        impl Decode for Counted {
+            async fn decode_from<R>(config: &Config, reader: R) -> Result<Self>
+            where
+                R: Read + Unpin + Send,
+            {
+                DECODED.fetch_add(1, Ordering::Relaxed);
+                u8::decode_from(config, reader).await.map(Counted)
+            }
+        }
+
+        // `Counted` is encoded as `u8`
+        let bytes = (0..10_000).map(|i| i as u8).collect::<Vec<_>>();
+        let encoded = executor::block_on(encode(&bytes));
+        let original = bytes.into_iter().map(Counted).collect::<Vec<_>>();
+
+        // Feeding one byte at a time decodes every element exactly once
+        let mut decoder = Decoder::<Vec<Counted>>::new();
+        let mut decoded = None;
+
+        for byte in encoded.chunks(1) {
+            if let Progress::Done(value, _) = decoder.feed(byte).unwrap() {
+                decoded = Some(value);
+            }
+        }
+
+        assert_eq!(Some(original), decoded);
+        assert_eq!(10_000, DECODED.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn seq_encoder_test() {
+        use crate::{Error, SeqEncoder};
+
+        executor::block_on(async {
+            let values = vec!["hello".to_string(), "world".to_string()];
+
+            let mut bytes = Vec::new();
+            let mut encoder = SeqEncoder::new(values.len(), &mut bytes).await.unwrap();
+
+            encoder.encode(&values[0]).await.unwrap();
+            assert_eq!(1, encoder.remaining());
+            encoder.encode_iter(&values[1..]).await.unwrap();
+
+            assert!(matches!(
+                encoder.encode(&"extra".to_string()).await,
+                Err(Error::LengthMismatch {
+                    expected: 2,
+                    actual: 3
+                })
+            ));
+            let written = encoder.finish().await.unwrap();
+
+            assert_eq!(bytes.len(), written);
+            assert_eq!(encode(&values).await, bytes);
+
+            let mut bytes = Vec::new();
+            let mut encoder = SeqEncoder::new(3, &mut bytes).await.unwrap();
+            encoder.encode_iter(1u32..3).await.unwrap();
+
+            assert!(matches!(
+                encoder.finish().await,
+                Err(Error::LengthMismatch {
+                    expected: 3,
+                    actual: 2
+                })
+            ));
+        });
+    }
+
+    #[test]
+    fn chunked_test() {
+        use crate::{Chunked, ChunkedEncoder};
+
+        executor::block_on(async {
+            let chunked = Chunked(vec![1u16, 2, 3]);
+            let encoded = encode(&chunked).await;
+
+            assert_eq!(vec![3, 1, 0, 2, 0, 3, 0, 0], encoded);
+            assert_eq!(encoded.len(), chunked.size());
+            assert_eq!(chunked, decode(&encoded).await.unwrap());
+
+            let empty = Chunked::<u16>::default();
+            assert_eq!(vec![0], encode(&empty).await);
+
+            let large = Chunked((0..3000u32).collect());
+            let encoded = encode(&large).await;
+
+            assert_eq!(encoded.len(), large.size());
+            assert_eq!(large, decode(&encoded).await.unwrap());
+
+            let mut bytes = Vec::new();
+            let mut encoder = ChunkedEncoder::new(&mut bytes);
+            encoder.set_max_chunk_len(2);
+
+            encoder.encode_iter(1u16..4).await.unwrap();
+            assert_eq!(1, encoder.buffered());
+
+            let written = encoder.finish().await.unwrap();
+
+            assert_eq!(vec![2, 1, 0, 2, 0, 1, 3, 0, 0], bytes);
+            assert_eq!(bytes.len(), written);
+            assert_eq!(chunked, decode(&bytes).await.unwrap());
+
+            assert!(decode::<Chunked<u16>, _>(&bytes[..bytes.len() - 1])
+                .await
+                .is_err());
+        });
+    }
+
+    #[test]
+    fn numeric_seq_test() {
+        use crate::{Config, Endianness};
+
+        for endianness in [Endianness::LittleEndian, Endianness::BigEndian] {
+            let config = Config::default().with_endianness(endianness);
+
+            let floats: Vec<f32> = (0..100).map(|_| random()).collect();
+            let arr: [i16; 1024] = [0; 1024].map(|_: i16| random());
+
+            let mut expected = executor::block_on(config.encode(&VarInt::from(100u8)));
+            for value in floats.iter() {
+                expected.extend_from_slice(&executor::block_on(config.encode(value)));
+            }
+
+            let encoded = executor::block_on(config.encode(&floats));
+            assert_eq!(
+                expected, encoded,
+                "Wire format must match element-wise encoding"
+            );
+
+            let mut async_encoded = Vec::new();
+            executor::block_on(floats.encode_to(&config, &mut async_encoded)).unwrap();
+            assert_eq!(expected, async_encoded);
+
+            let decoded: Vec<f32> = executor::block_on(config.decode_from(&encoded[..])).unwrap();
+            assert_eq!(floats, decoded, "Invalid encoding/decoding");
+
+            // Decoding in place reuses the allocation of vector
+            let mut reused = Vec::with_capacity(128);
+            reused.push(1.0f32);
+            let ptr = reused.as_ptr();
+            executor::block_on(reused.decode_in_place(&config, &encoded[..])).unwrap();
+            assert_eq!(floats, reused);
+            assert_eq!(ptr, reused.as_ptr());
+
+            let encoded = executor::block_on(config.encode(&arr));
+            assert_eq!(arr.size(), encoded.len());
+            let decoded: [i16; 1024] =
+                executor::block_on(config.decode_from(&encoded[..])).unwrap();
+            assert_eq!(arr, decoded, "Invalid encoding/decoding");
+
+            // Elements of a wrapped `VecDeque` are stored in two slices
+            let mut deque = VecDeque::with_capacity(128);
+            deque.extend(0..100u64);
+            deque.drain(..50);
+            deque.extend(100..150u64);
+            assert!(!deque.as_slices().1.is_empty());
+
+            let expected = executor::block_on(config.encode(&Vec::from(deque.clone())));
+            assert_eq!(expected, executor::block_on(config.encode(&deque)));
+
+            let mut async_encoded = Vec::new();
+            executor::block_on(deque.encode_to(&config, &mut async_encoded)).unwrap();
+            assert_eq!(expected, async_encoded);
+        }
+    }
+
+    #[test]
+    fn decode_in_place_test() {
+        executor::block_on(async {
+            let mut map: HashMap<u8, String> = HashMap::new();
+            map.insert(1, "one".to_string());
+            map.insert(2, "two".to_string());
+
+            let mut decoded: HashMap<u8, String> = HashMap::new();
+            decoded.insert(3, "three".to_string());
+
+            let encoded = encode(&map).await;
+            decoded
+                .decode_in_place(crate::config(), &encoded[..])
+                .await
+                .unwrap();
+            assert_eq!(map, decoded, "Invalid encoding/decoding");
+
+            let mut bytes = vec![0u8; 64];
+            let ptr = bytes.as_ptr();
+
+            let encoded = encode(&vec![1u8, 2, 3]).await;
+            bytes
+                .decode_in_place(crate::config(), &encoded[..])
+                .await
+                .unwrap();
+            assert_eq!(vec![1u8, 2, 3], bytes);
+            assert_eq!(ptr, bytes.as_ptr());
+
+            let mut string = "hello".to_string();
+            let encoded = encode("\u{ff}\u{ff}").await;
+            string
+                .decode_in_place(crate::config(), &encoded[..])
+                .await
+                .unwrap();
+            assert_eq!("\u{ff}\u{ff}", string);
+        });
+    }
+
     #[test]
     fn box_test() {
         executor::block_on(async {
@@ -503,4 +1282,47 @@ mod tests {
 
     varint_try_into_test!(usize, varint_usize_test);
     varint_try_into_test!(isize, varint_isize_test);
+
+    #[test]
+    fn varint_overflow_test() {
+        executor::block_on(async {
+            let encoded = [0xff; 25];
+            assert!(matches!(
+                decode::<VarInt, _>(&encoded).await,
+                Err(Error::InvalidVarInt)
+            ));
+            assert!(matches!(
+                decode_from::<VarInt, _>(&encoded[..]).await,
+                Err(Error::InvalidVarInt)
+            ));
+        });
+    }
+
+    macro_rules! huge_length_test {
+        ($type: ty, $name: ident) => {
+            #[test]
+            fn $name() {
+                executor::block_on(async {
+                    // Length prefix of 2^56 - 1 elements without any elements
+                    let encoded = [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x0f];
+                    assert!(matches!(
+                        decode::<$type, _>(&encoded).await,
+                        Err(Error::UnexpectedEof)
+                    ));
+                    assert!(matches!(
+                        decode_from::<$type, _>(&encoded[..]).await,
+                        Err(Error::UnexpectedEof)
+                    ));
+                });
+            }
+        };
+    }
+
+    huge_length_test!(Vec<u8>, huge_length_vec_u8_test);
+    huge_length_test!(Vec<u32>, huge_length_vec_u32_test);
+    huge_length_test!(Vec<String>, huge_length_vec_string_test);
+    huge_length_test!(VecDeque<String>, huge_length_vec_deque_test);
+    huge_length_test!(HashSet<String>, huge_length_hash_set_test);
+    huge_length_test!(HashMap<String, String>, huge_length_hash_map_test);
+    huge_length_test!(String, huge_length_string_test);
 }
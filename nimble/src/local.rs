@@ -0,0 +1,5 @@
+//! Single-threaded counterparts of `Encode` and `Decode` (futures and values are not required to be `Send`/`Sync`)
+mod decode;
+mod encode;
+
+pub use self::{decode::LocalDecode, encode::LocalEncode};
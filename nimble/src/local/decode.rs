@@ -0,0 +1,506 @@
+use alloc::{
+    borrow::{Cow, ToOwned},
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet, BinaryHeap, LinkedList, VecDeque},
+    ffi::CString,
+    rc::Rc,
+    string::String,
+    sync::Arc,
+    vec::Vec,
+};
+#[cfg(feature = "std")]
+use core::hash::{BuildHasher, Hash};
+use core::{
+    cell::RefCell,
+    convert::TryFrom,
+    future::Future,
+    marker::PhantomData,
+    mem,
+    num::{
+        NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+        NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+    },
+};
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
+
+#[cfg(feature = "bytes")]
+use bytes::{Bytes, BytesMut};
+
+use crate::{
+    decode::{read_bytes_into, read_values_into, reader_capacity},
+    io::{poll_in_memory, Read, ReadExt},
+    marker::{LocalBoxFuture, LocalDynRead},
+    varint::MAX_ENCODED_LEN,
+    Config, Decode, Encode, Error, Result, VarInt,
+};
+
+/// Trait for decoding values on a single thread
+///
+/// Same as [`Decode`](../trait.Decode.html) (and accepts the same bytes), except that returned futures are not
+/// required to be `Send` and readers are not required to be `Send`. This allows decoding `Rc<RefCell<..>>` based types
+/// on a single-threaded executor.
+pub trait LocalDecode: Sized {
+    /// Decodes values from reader
+    ///
+    /// ## Equivalent to:
+    ///
+    /// ```rust,ignore
+    /// async fn decode_from_local<R>(config: &Config, reader: R) -> Result<Self>
+    /// where
+    ///     R: Read + Unpin
+    /// ```
+    fn decode_from_local<R>(config: &Config, reader: R) -> impl Future<Output = Result<Self>>
+    where
+        R: Read + Unpin;
+
+    /// Decodes value from the start of `bytes` synchronously and advances `bytes` past the decoded value
+    ///
+    /// Default implementation drives [`decode_from_local`](#tymethod.decode_from_local) to completion without an
+    /// executor (reading from a slice never blocks).
+    fn decode_from_slice_local(config: &Config, bytes: &mut &[u8]) -> Result<Self> {
+        poll_in_memory(Self::decode_from_local(config, bytes))
+    }
+
+    /// Decodes `len` values (encoded without length prefix) from reader
+    ///
+    /// Used by `Vec<T>` to decode its elements. Default implementation decodes values one by one. Primitive types
+    /// override this method to read all the values at once.
+    fn decode_vec_from_local<R>(
+        len: usize,
+        config: &Config,
+        mut reader: R,
+    ) -> impl Future<Output = Result<Vec<Self>>>
+    where
+        R: Read + Unpin,
+    {
+        async move {
+            let mut values = Vec::with_capacity(reader_capacity::<Self>(len));
+
+            for _ in 0..len {
+                values.push(Self::decode_from_local(config, &mut reader).await?);
+            }
+
+            Ok(values)
+        }
+    }
+}
+
+/// Decodes a sequence length prefix
+async fn decode_len<R>(config: &Config, reader: R) -> Result<usize>
+where
+    R: Read + Unpin,
+{
+    usize::try_from(VarInt::decode_from_local(config, reader).await?)
+}
+
+/// Reads a length prefixed byte array and returns it along with its prefix (so that it can be decoded with `Decode`)
+async fn read_prefixed<R>(config: &Config, mut reader: R) -> Result<Vec<u8>>
+where
+    R: Read + Unpin,
+{
+    let len = VarInt::decode_from_local(config, &mut reader).await?;
+
+    let mut bytes = Vec::new();
+    len.encode_to_vec(config, &mut bytes);
+    read_bytes_into(&mut bytes, usize::try_from(len)?, reader).await?;
+
+    Ok(bytes)
+}
+
+macro_rules! impl_fixed_size {
+    (@impl $type: ty { $($items: tt)* }) => {
+        impl LocalDecode for $type {
+            async fn decode_from_local<R>(config: &Config, mut reader: R) -> Result<Self>
+            where
+                R: Read + Unpin,
+            {
+                let mut bytes = [0u8; mem::size_of::<$type>()];
+                reader.read_exact(&mut bytes).await?;
+                Self::decode_from_slice(config, &mut &bytes[..])
+            }
+
+            #[inline]
+            fn decode_from_slice_local(config: &Config, bytes: &mut &[u8]) -> Result<Self> {
+                Self::decode_from_slice(config, bytes)
+            }
+
+            $($items)*
+        }
+    };
+    ($($type: ty),+) => {
+        $(
+            impl_fixed_size!(@impl $type {});
+        )+
+    };
+}
+
+/// Primitives read all the elements of a `Vec` at once
+macro_rules! impl_primitive {
+    ($($type: ty),+) => {
+        $(
+            impl_fixed_size!(@impl $type {
+                async fn decode_vec_from_local<R>(len: usize, config: &Config, reader: R) -> Result<Vec<Self>>
+                where
+                    R: Read + Unpin,
+                {
+                    let mut values = Vec::new();
+                    read_values_into(&mut values, len, config, reader).await?;
+                    Ok(values)
+                }
+            });
+        )+
+    };
+}
+
+impl_fixed_size!(@impl u8 {
+    async fn decode_vec_from_local<R>(len: usize, _config: &Config, reader: R) -> Result<Vec<Self>>
+    where
+        R: Read + Unpin,
+    {
+        let mut values = Vec::new();
+        read_bytes_into(&mut values, len, reader).await?;
+        Ok(values)
+    }
+});
+impl_primitive!(u16, u32, u64, u128, i8, i16, i32, i64, i128, usize, isize, f32, f64);
+impl_fixed_size!(bool, char, ());
+impl_fixed_size!(
+    NonZeroU8,
+    NonZeroU16,
+    NonZeroU32,
+    NonZeroU64,
+    NonZeroU128,
+    NonZeroI8,
+    NonZeroI16,
+    NonZeroI32,
+    NonZeroI64,
+    NonZeroI128,
+    NonZeroUsize,
+    NonZeroIsize
+);
+
+impl LocalDecode for VarInt {
+    async fn decode_from_local<R>(config: &Config, mut reader: R) -> Result<Self>
+    where
+        R: Read + Unpin,
+    {
+        let mut bytes = [0u8; MAX_ENCODED_LEN];
+
+        for len in 1..=MAX_ENCODED_LEN {
+            reader.read_exact(&mut bytes[len - 1..len]).await?;
+
+            if bytes[len - 1] & 0b1000_0000 == 0 {
+                return Self::decode_from_slice(config, &mut &bytes[..len]);
+            }
+        }
+
+        Err(Error::InvalidVarInt)
+    }
+
+    #[inline]
+    fn decode_from_slice_local(config: &Config, bytes: &mut &[u8]) -> Result<Self> {
+        Self::decode_from_slice(config, bytes)
+    }
+}
+
+macro_rules! impl_prefixed {
+    ($($type: ty),+) => {
+        $(
+            impl LocalDecode for $type {
+                async fn decode_from_local<R>(config: &Config, reader: R) -> Result<Self>
+                where
+                    R: Read + Unpin,
+                {
+                    let bytes = read_prefixed(config, reader).await?;
+                    Self::decode_from_slice(config, &mut &bytes[..])
+                }
+
+                #[inline]
+                fn decode_from_slice_local(config: &Config, bytes: &mut &[u8]) -> Result<Self> {
+                    Self::decode_from_slice(config, bytes)
+                }
+            }
+        )+
+    };
+}
+
+impl_prefixed!(String, CString);
+#[cfg(feature = "bytes")]
+impl_prefixed!(Bytes, BytesMut);
+
+impl<T> LocalDecode for Option<T>
+where
+    T: LocalDecode,
+{
+    async fn decode_from_local<R>(config: &Config, mut reader: R) -> Result<Self>
+    where
+        R: Read + Unpin,
+    {
+        let option = u8::decode_from_local(config, &mut reader).await?;
+
+        match option {
+            0 => Ok(None),
+            1 => T::decode_from_local(config, &mut reader).await.map(Some),
+            _ => Err(Error::InvalidEnumVariant(option.into())),
+        }
+    }
+}
+
+impl<T, E> LocalDecode for core::result::Result<T, E>
+where
+    T: LocalDecode,
+    E: LocalDecode,
+{
+    async fn decode_from_local<R>(config: &Config, mut reader: R) -> Result<Self>
+    where
+        R: Read + Unpin,
+    {
+        let option = u8::decode_from_local(config, &mut reader).await?;
+
+        match option {
+            0 => T::decode_from_local(config, &mut reader).await.map(Ok),
+            1 => E::decode_from_local(config, &mut reader).await.map(Err),
+            _ => Err(Error::InvalidEnumVariant(option.into())),
+        }
+    }
+}
+
+impl<T> LocalDecode for Vec<T>
+where
+    T: LocalDecode,
+{
+    async fn decode_from_local<R>(config: &Config, mut reader: R) -> Result<Self>
+    where
+        R: Read + Unpin,
+    {
+        let len = decode_len(config, &mut reader).await?;
+        T::decode_vec_from_local(len, config, reader).await
+    }
+}
+
+macro_rules! impl_seq {
+    (
+        $ty: ident < T $(: $tbound1: ident $(+ $tbound2: ident)*)* $(, $typaram: ident : $bound1: ident $(+ $bound2: ident)*)* >,
+        $len: ident,
+        $create: expr,
+        $insert: expr
+    ) => {
+        impl<T $(, $typaram)*> LocalDecode for $ty<T $(, $typaram)*>
+        where
+            T: LocalDecode $(+ $tbound1 $(+ $tbound2)*)*,
+            $($typaram: $bound1 $(+ $bound2)*,)*
+        {
+            async fn decode_from_local<R>(config: &Config, mut reader: R) -> Result<Self>
+            where
+                R: Read + Unpin,
+            {
+                let len = decode_len(config, &mut reader).await?;
+
+                let mut value = {
+                    #[allow(unused_variables)]
+                    let $len = reader_capacity::<T>(len);
+                    $create
+                };
+
+                for _ in 0..len {
+                    $insert(&mut value, T::decode_from_local(config, &mut reader).await?);
+                }
+
+                Ok(value)
+            }
+        }
+    };
+}
+
+impl_seq!(
+    VecDeque<T>,
+    len,
+    VecDeque::with_capacity(len),
+    VecDeque::push_back
+);
+impl_seq!(LinkedList<T>, len, LinkedList::new(), LinkedList::push_back);
+#[cfg(feature = "std")]
+impl_seq!(
+    HashSet<T: Eq + Hash, S: BuildHasher + Default>,
+    len,
+    HashSet::with_capacity_and_hasher(len, S::default()),
+    HashSet::insert
+);
+impl_seq!(BTreeSet<T: Ord>, len, BTreeSet::new(), BTreeSet::insert);
+impl_seq!(BinaryHeap<T: Ord>, len, BinaryHeap::new(), BinaryHeap::push);
+
+/// Futures of values behind smart pointers are boxed so that recursive types have futures of finite size
+macro_rules! impl_deref {
+    ($type: ty, $func: expr) => {
+        impl<T> LocalDecode for $type
+        where
+            T: LocalDecode,
+        {
+            async fn decode_from_local<R>(config: &Config, mut reader: R) -> Result<Self>
+            where
+                R: Read + Unpin,
+            {
+                let reader: &mut LocalDynRead<'_> = &mut reader;
+                let future: LocalBoxFuture<'_, Result<T>> =
+                    Box::pin(T::decode_from_local(config, reader));
+                future.await.map($func)
+            }
+        }
+    };
+}
+
+impl_deref!(Box<T>, Box::new);
+impl_deref!(Rc<T>, Rc::new);
+impl_deref!(Arc<T>, Arc::new);
+
+impl<T> LocalDecode for RefCell<T>
+where
+    T: LocalDecode,
+{
+    #[inline]
+    async fn decode_from_local<R>(config: &Config, reader: R) -> Result<Self>
+    where
+        R: Read + Unpin,
+    {
+        T::decode_from_local(config, reader).await.map(RefCell::new)
+    }
+}
+
+impl<'a, T: ?Sized> LocalDecode for Cow<'a, T>
+where
+    T: 'a + ToOwned,
+    <T as ToOwned>::Owned: LocalDecode,
+{
+    async fn decode_from_local<R>(config: &Config, reader: R) -> Result<Self>
+    where
+        R: Read + Unpin,
+    {
+        let owned = <<T as ToOwned>::Owned>::decode_from_local(config, reader).await?;
+        Ok(Cow::Owned(owned))
+    }
+}
+
+macro_rules! impl_map {
+    (
+        $ty: ident < K $(: $kbound1: ident $(+ $kbound2: ident)*)*, V $(, $typaram: ident : $bound1: ident $(+ $bound2: ident)*)* >,
+        $len: ident,
+        $create: expr
+    ) => {
+        impl<K, V $(, $typaram)*> LocalDecode for $ty<K, V $(, $typaram)*>
+        where
+            K: LocalDecode $(+ $kbound1 $(+ $kbound2)*)*,
+            V: LocalDecode,
+            $($typaram: $bound1 $(+ $bound2)*,)*
+        {
+            async fn decode_from_local<R>(config: &Config, mut reader: R) -> Result<Self>
+            where
+                R: Read + Unpin,
+            {
+                let len = decode_len(config, &mut reader).await?;
+
+                let mut map = {
+                    #[allow(unused_variables)]
+                    let $len = reader_capacity::<(K, V)>(len);
+                    $create
+                };
+
+                for _ in 0..len {
+                    let key = K::decode_from_local(config, &mut reader).await?;
+                    let value = V::decode_from_local(config, &mut reader).await?;
+                    map.insert(key, value);
+                }
+
+                Ok(map)
+            }
+        }
+    };
+}
+
+#[cfg(feature = "std")]
+impl_map!(
+    HashMap<K: Eq + Hash, V, S: BuildHasher + Default>,
+    len,
+    HashMap::with_capacity_and_hasher(len, S::default())
+);
+impl_map!(BTreeMap<K: Ord, V>, len, BTreeMap::new());
+
+macro_rules! impl_fixed_arr {
+    ($($len: expr),+) => {
+        $(
+            impl<T> LocalDecode for [T; $len]
+            where
+                T: LocalDecode,
+            {
+                async fn decode_from_local<R>(config: &Config, mut reader: R) -> Result<Self>
+                where
+                    R: Read + Unpin,
+                {
+                    let mut values = Vec::with_capacity($len);
+
+                    for _ in 0..$len {
+                        values.push(T::decode_from_local(config, &mut reader).await?);
+                    }
+
+                    Self::try_from(values).map_err(|_| Error::PartiallyFilledArray)
+                }
+            }
+        )+
+    };
+}
+
+impl_fixed_arr!(
+    1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26,
+    27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50,
+    51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63, 64, 128, 256, 512, 1024
+);
+
+macro_rules! impl_tuple {
+    ($(($($name:tt)+))+) => {
+        $(
+            impl<$($name),+> LocalDecode for ($($name,)+)
+            where
+                $($name: LocalDecode,)+
+            {
+                async fn decode_from_local<R>(config: &Config, mut reader: R) -> Result<Self>
+                where
+                    R: Read + Unpin,
+                {
+                    Ok((
+                        $(
+                            $name::decode_from_local(config, &mut reader).await?,
+                        )+
+                    ))
+                }
+            }
+        )+
+    }
+}
+
+impl_tuple! {
+    (T0)
+    (T0 T1)
+    (T0 T1 T2)
+    (T0 T1 T2 T3)
+    (T0 T1 T2 T3 T4)
+    (T0 T1 T2 T3 T4 T5)
+    (T0 T1 T2 T3 T4 T5 T6)
+    (T0 T1 T2 T3 T4 T5 T6 T7)
+    (T0 T1 T2 T3 T4 T5 T6 T7 T8)
+    (T0 T1 T2 T3 T4 T5 T6 T7 T8 T9)
+    (T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10)
+    (T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11)
+    (T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12)
+    (T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13)
+    (T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13 T14)
+    (T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13 T14 T15)
+}
+
+impl<T: ?Sized> LocalDecode for PhantomData<T> {
+    async fn decode_from_local<R>(_config: &Config, _reader: R) -> Result<Self>
+    where
+        R: Read + Unpin,
+    {
+        Ok(PhantomData)
+    }
+}
@@ -0,0 +1,690 @@
+use alloc::{
+    borrow::{Cow, ToOwned},
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet, BinaryHeap, LinkedList, VecDeque},
+    ffi::CString,
+    rc::Rc,
+    string::String,
+    sync::Arc,
+    vec::Vec,
+};
+use core::ffi::CStr;
+use core::{
+    cell::RefCell,
+    convert::TryFrom,
+    future::Future,
+    marker::PhantomData,
+    num::{
+        NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+        NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+    },
+};
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
+
+#[cfg(feature = "bytes")]
+use bytes::{Bytes, BytesMut};
+
+use crate::{
+    encode::write_values,
+    io::{poll_in_memory, Write, WriteExt},
+    marker::{LocalBoxFuture, LocalDynWrite},
+    Config, Encode, Endianness, Result, VarInt,
+};
+
+/// Trait for encoding values on a single thread
+///
+/// Same as [`Encode`](../trait.Encode.html) (and produces the same bytes), except that returned futures are not
+/// required to be `Send` and writers and values are not required to be `Send`/`Sync`. This allows encoding
+/// `Rc<RefCell<..>>` based types on a single-threaded executor.
+pub trait LocalEncode {
+    /// Writes encoded byte array to writer and returns the number of bytes written
+    ///
+    /// ## Equivalent to:
+    ///
+    /// ```rust,ignore
+    /// async fn encode_to_local<W>(&self, config: &Config, writer: W) -> Result<usize>
+    /// where
+    ///     W: Write + Unpin
+    /// ```
+    fn encode_to_local<W>(&self, config: &Config, writer: W) -> impl Future<Output = Result<usize>>
+    where
+        W: Write + Unpin;
+
+    /// Appends encoded byte array to `buf` synchronously and returns the number of bytes written
+    ///
+    /// Default implementation drives [`encode_to_local`](#tymethod.encode_to_local) to completion without an executor
+    /// (writing to a `Vec` never blocks).
+    fn encode_to_vec_local(&self, config: &Config, buf: &mut Vec<u8>) -> usize {
+        poll_in_memory(self.encode_to_local(config, buf))
+            .expect("Writing to a `Vec` never fails. Log an issue on nimble's GitHub repository with backtrace.")
+    }
+
+    /// Writes encoded byte arrays of all the values in `slice` (without length prefix) to writer and returns the number
+    /// of bytes written
+    ///
+    /// Used by sequence types to encode their elements. Default implementation encodes values one by one. Primitive
+    /// types override this method to write all the values at once.
+    fn encode_slice_to_local<W>(
+        slice: &[Self],
+        config: &Config,
+        mut writer: W,
+    ) -> impl Future<Output = Result<usize>>
+    where
+        Self: Sized,
+        W: Write + Unpin,
+    {
+        async move {
+            let mut encoded = 0;
+
+            for item in slice {
+                encoded += item.encode_to_local(config, &mut writer).await?;
+            }
+
+            Ok(encoded)
+        }
+    }
+
+    /// Appends encoded byte arrays of all the values in `slice` (without length prefix) to `buf` synchronously and
+    /// returns the number of bytes written
+    fn encode_slice_to_vec_local(slice: &[Self], config: &Config, buf: &mut Vec<u8>) -> usize
+    where
+        Self: Sized,
+    {
+        slice
+            .iter()
+            .map(|item| item.encode_to_vec_local(config, buf))
+            .sum()
+    }
+}
+
+/// Returns encoded length prefix of a sequence
+#[inline]
+fn len_prefix(len: usize) -> VarInt {
+    VarInt::try_from(len).expect(
+        "Failed to convert usize to u128. Log an issue on nimble's GitHub repository with backtrace.",
+    )
+}
+
+macro_rules! impl_primitive {
+    ($($type: ty),+) => {
+        $(
+            impl LocalEncode for $type {
+                async fn encode_to_local<W>(&self, config: &Config, mut writer: W) -> Result<usize>
+                where
+                    W: Write + Unpin,
+                {
+                    let bytes = match config.endianness {
+                        Endianness::LittleEndian => self.to_le_bytes(),
+                        Endianness::BigEndian => self.to_be_bytes(),
+                    };
+
+                    writer.write_all(&bytes).await?;
+                    Ok(bytes.len())
+                }
+
+                #[inline]
+                fn encode_to_vec_local(&self, config: &Config, buf: &mut Vec<u8>) -> usize {
+                    self.encode_to_vec(config, buf)
+                }
+
+                #[inline]
+                fn encode_slice_to_local<W>(
+                    slice: &[Self],
+                    config: &Config,
+                    writer: W,
+                ) -> impl Future<Output = Result<usize>>
+                where
+                    W: Write + Unpin,
+                {
+                    write_values(slice, config, writer)
+                }
+
+                #[inline]
+                fn encode_slice_to_vec_local(slice: &[Self], config: &Config, buf: &mut Vec<u8>) -> usize {
+                    Self::encode_slice_to_vec(slice, config, buf)
+                }
+            }
+        )+
+    };
+}
+
+impl_primitive!(u16, u32, u64, u128, i8, i16, i32, i64, i128, usize, isize, f32, f64);
+
+impl LocalEncode for u8 {
+    async fn encode_to_local<W>(&self, _config: &Config, mut writer: W) -> Result<usize>
+    where
+        W: Write + Unpin,
+    {
+        writer.write_all(&[*self]).await?;
+        Ok(1)
+    }
+
+    #[inline]
+    fn encode_to_vec_local(&self, config: &Config, buf: &mut Vec<u8>) -> usize {
+        self.encode_to_vec(config, buf)
+    }
+
+    async fn encode_slice_to_local<W>(
+        slice: &[Self],
+        _config: &Config,
+        mut writer: W,
+    ) -> Result<usize>
+    where
+        W: Write + Unpin,
+    {
+        writer.write_all(slice).await?;
+        Ok(slice.len())
+    }
+
+    #[inline]
+    fn encode_slice_to_vec_local(slice: &[Self], config: &Config, buf: &mut Vec<u8>) -> usize {
+        Self::encode_slice_to_vec(slice, config, buf)
+    }
+}
+
+/// Implements `LocalEncode` for types which are encoded as another fixed size type
+macro_rules! impl_converted {
+    ($($type: ty => $convert: expr),+ $(,)?) => {
+        $(
+            impl LocalEncode for $type {
+                #[allow(clippy::trivially_copy_pass_by_ref)]
+                async fn encode_to_local<W>(&self, config: &Config, writer: W) -> Result<usize>
+                where
+                    W: Write + Unpin,
+                {
+                    ($convert)(self).encode_to_local(config, writer).await
+                }
+
+                #[inline]
+                #[allow(clippy::trivially_copy_pass_by_ref)]
+                fn encode_to_vec_local(&self, config: &Config, buf: &mut Vec<u8>) -> usize {
+                    self.encode_to_vec(config, buf)
+                }
+            }
+        )+
+    };
+}
+
+impl_converted!(
+    bool => |value: &bool| *value as u8,
+    char => |value: &char| *value as u32,
+    NonZeroU8 => |value: &NonZeroU8| value.get(),
+    NonZeroU16 => |value: &NonZeroU16| value.get(),
+    NonZeroU32 => |value: &NonZeroU32| value.get(),
+    NonZeroU64 => |value: &NonZeroU64| value.get(),
+    NonZeroU128 => |value: &NonZeroU128| value.get(),
+    NonZeroI8 => |value: &NonZeroI8| value.get(),
+    NonZeroI16 => |value: &NonZeroI16| value.get(),
+    NonZeroI32 => |value: &NonZeroI32| value.get(),
+    NonZeroI64 => |value: &NonZeroI64| value.get(),
+    NonZeroI128 => |value: &NonZeroI128| value.get(),
+    NonZeroUsize => |value: &NonZeroUsize| value.get(),
+    NonZeroIsize => |value: &NonZeroIsize| value.get(),
+);
+
+impl LocalEncode for () {
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    async fn encode_to_local<W>(&self, _config: &Config, _writer: W) -> Result<usize>
+    where
+        W: Write + Unpin,
+    {
+        Ok(0)
+    }
+
+    #[inline]
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    fn encode_to_vec_local(&self, _config: &Config, _buf: &mut Vec<u8>) -> usize {
+        0
+    }
+}
+
+impl LocalEncode for VarInt {
+    async fn encode_to_local<W>(&self, _config: &Config, mut writer: W) -> Result<usize>
+    where
+        W: Write + Unpin,
+    {
+        let (bytes, encoded) = self.to_array();
+        writer.write_all(&bytes[..encoded]).await?;
+        Ok(encoded)
+    }
+
+    #[inline]
+    fn encode_to_vec_local(&self, config: &Config, buf: &mut Vec<u8>) -> usize {
+        self.encode_to_vec(config, buf)
+    }
+}
+
+macro_rules! impl_as_bytes {
+    ($ty: tt, $as_bytes: tt) => {
+        impl LocalEncode for $ty {
+            #[inline]
+            #[allow(clippy::ptr_arg)]
+            fn encode_to_local<W>(
+                &self,
+                config: &Config,
+                writer: W,
+            ) -> impl Future<Output = Result<usize>>
+            where
+                W: Write + Unpin,
+            {
+                Self::$as_bytes(self).encode_to_local(config, writer)
+            }
+
+            #[inline]
+            #[allow(clippy::ptr_arg)]
+            fn encode_to_vec_local(&self, config: &Config, buf: &mut Vec<u8>) -> usize {
+                self.encode_to_vec(config, buf)
+            }
+        }
+    };
+}
+
+impl_as_bytes!(str, as_bytes);
+impl_as_bytes!(String, as_bytes);
+impl_as_bytes!(CStr, to_bytes);
+impl_as_bytes!(CString, as_bytes);
+#[cfg(feature = "bytes")]
+impl_as_bytes!(Bytes, as_ref);
+#[cfg(feature = "bytes")]
+impl_as_bytes!(BytesMut, as_ref);
+
+impl<T> LocalEncode for Option<T>
+where
+    T: LocalEncode,
+{
+    async fn encode_to_local<W>(&self, config: &Config, mut writer: W) -> Result<usize>
+    where
+        W: Write + Unpin,
+    {
+        match self {
+            None => 0u8.encode_to_local(config, &mut writer).await,
+            Some(ref value) => Ok(1u8.encode_to_local(config, &mut writer).await?
+                + value.encode_to_local(config, &mut writer).await?),
+        }
+    }
+
+    fn encode_to_vec_local(&self, config: &Config, buf: &mut Vec<u8>) -> usize {
+        match self {
+            None => 0u8.encode_to_vec_local(config, buf),
+            Some(ref value) => {
+                1u8.encode_to_vec_local(config, buf) + value.encode_to_vec_local(config, buf)
+            }
+        }
+    }
+}
+
+impl<T, E> LocalEncode for core::result::Result<T, E>
+where
+    T: LocalEncode,
+    E: LocalEncode,
+{
+    async fn encode_to_local<W>(&self, config: &Config, mut writer: W) -> Result<usize>
+    where
+        W: Write + Unpin,
+    {
+        match self {
+            Ok(ref value) => Ok(0u8.encode_to_local(config, &mut writer).await?
+                + value.encode_to_local(config, &mut writer).await?),
+            Err(ref err) => Ok(1u8.encode_to_local(config, &mut writer).await?
+                + err.encode_to_local(config, &mut writer).await?),
+        }
+    }
+
+    fn encode_to_vec_local(&self, config: &Config, buf: &mut Vec<u8>) -> usize {
+        match self {
+            Ok(ref value) => {
+                0u8.encode_to_vec_local(config, buf) + value.encode_to_vec_local(config, buf)
+            }
+            Err(ref err) => {
+                1u8.encode_to_vec_local(config, buf) + err.encode_to_vec_local(config, buf)
+            }
+        }
+    }
+}
+
+macro_rules! impl_seq {
+    ($ty: tt < T $(: $tbound1: tt $(+ $tbound2: ident)*)* $(, $typaram: tt $(: $bound1: tt $(+ $bound2: tt)*)*)* >) => {
+        impl<T $(, $typaram)*> LocalEncode for $ty<T $(, $typaram)*>
+        where
+            T: LocalEncode $(+ $tbound1 $(+ $tbound2)*)*,
+            $($typaram: $($bound1 $(+ $bound2)*)*,)*
+        {
+            async fn encode_to_local<W>(&self, config: &Config, mut writer: W) -> Result<usize>
+            where
+                W: Write + Unpin,
+            {
+                let mut encoded = len_prefix(self.len()).encode_to_local(config, &mut writer).await?;
+
+                for item in self.iter() {
+                    encoded += item.encode_to_local(config, &mut writer).await?;
+                }
+
+                Ok(encoded)
+            }
+
+            fn encode_to_vec_local(&self, config: &Config, buf: &mut Vec<u8>) -> usize {
+                let mut encoded = len_prefix(self.len()).encode_to_vec_local(config, buf);
+
+                for item in self.iter() {
+                    encoded += item.encode_to_vec_local(config, buf);
+                }
+
+                encoded
+            }
+        }
+    };
+}
+
+impl_seq!(LinkedList<T>);
+#[cfg(feature = "std")]
+impl_seq!(HashSet<T, S>);
+impl_seq!(BTreeSet<T>);
+
+/// Implements `LocalEncode` for sequences whose elements are stored in contiguous slices, which are encoded with
+/// `LocalEncode::encode_slice_to_local()`
+macro_rules! impl_slices_seq {
+    ($ty: ident, $seq: ident => $slices: expr) => {
+        impl<T> LocalEncode for $ty<T>
+        where
+            T: LocalEncode,
+        {
+            async fn encode_to_local<W>(&self, config: &Config, mut writer: W) -> Result<usize>
+            where
+                W: Write + Unpin,
+            {
+                let mut encoded = len_prefix(self.len())
+                    .encode_to_local(config, &mut writer)
+                    .await?;
+
+                let $seq = self;
+
+                for slice in $slices {
+                    encoded += T::encode_slice_to_local(slice, config, &mut writer).await?;
+                }
+
+                Ok(encoded)
+            }
+
+            fn encode_to_vec_local(&self, config: &Config, buf: &mut Vec<u8>) -> usize {
+                let mut encoded = len_prefix(self.len()).encode_to_vec_local(config, buf);
+
+                let $seq = self;
+
+                for slice in $slices {
+                    encoded += T::encode_slice_to_vec_local(slice, config, buf);
+                }
+
+                encoded
+            }
+        }
+    };
+}
+
+impl_slices_seq!(VecDeque, seq => {
+    let (front, back) = seq.as_slices();
+    [front, back]
+});
+impl_slices_seq!(BinaryHeap, seq => [seq.as_slice()]);
+
+impl<T> LocalEncode for [T]
+where
+    T: LocalEncode,
+{
+    async fn encode_to_local<W>(&self, config: &Config, mut writer: W) -> Result<usize>
+    where
+        W: Write + Unpin,
+    {
+        let encoded = len_prefix(self.len())
+            .encode_to_local(config, &mut writer)
+            .await?;
+
+        Ok(encoded + T::encode_slice_to_local(self, config, &mut writer).await?)
+    }
+
+    fn encode_to_vec_local(&self, config: &Config, buf: &mut Vec<u8>) -> usize {
+        len_prefix(self.len()).encode_to_vec_local(config, buf)
+            + T::encode_slice_to_vec_local(self, config, buf)
+    }
+}
+
+impl<T> LocalEncode for Vec<T>
+where
+    T: LocalEncode,
+{
+    #[inline]
+    fn encode_to_local<W>(&self, config: &Config, writer: W) -> impl Future<Output = Result<usize>>
+    where
+        W: Write + Unpin,
+    {
+        self.as_slice().encode_to_local(config, writer)
+    }
+
+    #[inline]
+    fn encode_to_vec_local(&self, config: &Config, buf: &mut Vec<u8>) -> usize {
+        self.as_slice().encode_to_vec_local(config, buf)
+    }
+}
+
+macro_rules! impl_deref {
+    ($($desc: tt)+) => {
+        impl $($desc)+ {
+            #[inline]
+            fn encode_to_local<W>(&self, config: &Config, writer: W) -> impl Future<Output = Result<usize>>
+            where
+                W: Write + Unpin,
+            {
+                <T>::encode_to_local(self, config, writer)
+            }
+
+            #[inline]
+            fn encode_to_vec_local(&self, config: &Config, buf: &mut Vec<u8>) -> usize {
+                <T>::encode_to_vec_local(self, config, buf)
+            }
+        }
+    };
+}
+
+impl_deref!(<T: ?Sized> LocalEncode for &T where T: LocalEncode);
+impl_deref!(<T: ?Sized> LocalEncode for &mut T where T: LocalEncode);
+
+/// Futures of values behind smart pointers are boxed so that recursive types have futures of finite size
+macro_rules! impl_boxed_deref {
+    ($($desc: tt)+) => {
+        impl $($desc)+ {
+            async fn encode_to_local<W>(&self, config: &Config, mut writer: W) -> Result<usize>
+            where
+                W: Write + Unpin,
+            {
+                let writer: &mut LocalDynWrite<'_> = &mut writer;
+                let future: LocalBoxFuture<'_, Result<usize>> =
+                    Box::pin(<T>::encode_to_local(self, config, writer));
+                future.await
+            }
+
+            #[inline]
+            fn encode_to_vec_local(&self, config: &Config, buf: &mut Vec<u8>) -> usize {
+                <T>::encode_to_vec_local(self, config, buf)
+            }
+        }
+    };
+}
+
+impl_boxed_deref!(<T: ?Sized> LocalEncode for Box<T> where T: LocalEncode);
+impl_boxed_deref!(<T: ?Sized> LocalEncode for Rc<T> where T: LocalEncode);
+impl_boxed_deref!(<T: ?Sized> LocalEncode for Arc<T> where T: LocalEncode);
+
+/// Encodes the borrowed value (panics if value is currently mutably borrowed)
+///
+/// Value is encoded in memory before writing it to writer so that it is not borrowed across `.await` points.
+impl<T: ?Sized> LocalEncode for RefCell<T>
+where
+    T: LocalEncode,
+{
+    async fn encode_to_local<W>(&self, config: &Config, mut writer: W) -> Result<usize>
+    where
+        W: Write + Unpin,
+    {
+        let mut bytes = Vec::new();
+        let encoded = self.encode_to_vec_local(config, &mut bytes);
+
+        writer.write_all(&bytes).await?;
+        Ok(encoded)
+    }
+
+    #[inline]
+    fn encode_to_vec_local(&self, config: &Config, buf: &mut Vec<u8>) -> usize {
+        self.borrow().encode_to_vec_local(config, buf)
+    }
+}
+
+impl<T: ?Sized> LocalEncode for Cow<'_, T>
+where
+    T: LocalEncode + ToOwned,
+{
+    #[inline]
+    fn encode_to_local<W>(&self, config: &Config, writer: W) -> impl Future<Output = Result<usize>>
+    where
+        W: Write + Unpin,
+    {
+        self.as_ref().encode_to_local(config, writer)
+    }
+
+    #[inline]
+    fn encode_to_vec_local(&self, config: &Config, buf: &mut Vec<u8>) -> usize {
+        self.as_ref().encode_to_vec_local(config, buf)
+    }
+}
+
+macro_rules! impl_map {
+    ($ty: tt < K, V $(, $typaram: tt)* >) => {
+        impl<K, V $(, $typaram)*> LocalEncode for $ty<K, V $(, $typaram)*>
+        where
+            K: LocalEncode,
+            V: LocalEncode,
+        {
+            async fn encode_to_local<W>(&self, config: &Config, mut writer: W) -> Result<usize>
+            where
+                W: Write + Unpin,
+            {
+                let mut encoded = len_prefix(self.len()).encode_to_local(config, &mut writer).await?;
+
+                for (key, value) in self.iter() {
+                    encoded += key.encode_to_local(config, &mut writer).await?;
+                    encoded += value.encode_to_local(config, &mut writer).await?;
+                }
+
+                Ok(encoded)
+            }
+
+            fn encode_to_vec_local(&self, config: &Config, buf: &mut Vec<u8>) -> usize {
+                let mut encoded = len_prefix(self.len()).encode_to_vec_local(config, buf);
+
+                for (key, value) in self.iter() {
+                    encoded += key.encode_to_vec_local(config, buf);
+                    encoded += value.encode_to_vec_local(config, buf);
+                }
+
+                encoded
+            }
+        }
+    };
+}
+
+#[cfg(feature = "std")]
+impl_map!(HashMap<K, V, S>);
+impl_map!(BTreeMap<K, V>);
+
+macro_rules! impl_fixed_arr {
+    ($($len: tt),+) => {
+        $(
+            impl<T> LocalEncode for [T; $len]
+            where
+                T: LocalEncode,
+            {
+                #[inline]
+                fn encode_to_local<W>(&self, config: &Config, writer: W) -> impl Future<Output = Result<usize>>
+                where
+                    W: Write + Unpin,
+                {
+                    T::encode_slice_to_local(self, config, writer)
+                }
+
+                #[inline]
+                fn encode_to_vec_local(&self, config: &Config, buf: &mut Vec<u8>) -> usize {
+                    T::encode_slice_to_vec_local(self, config, buf)
+                }
+            }
+        )+
+    };
+}
+
+impl_fixed_arr!(
+    1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26,
+    27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50,
+    51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63, 64, 128, 256, 512, 1024
+);
+
+macro_rules! impl_tuple {
+    ($(($($n:tt $name:tt)+))+) => {
+        $(
+            impl<$($name),+> LocalEncode for ($($name,)+)
+            where
+                $($name: LocalEncode,)+
+            {
+                async fn encode_to_local<W>(&self, config: &Config, mut writer: W) -> Result<usize>
+                where
+                    W: Write + Unpin,
+                {
+                    let mut encoded = 0;
+
+                    $(
+                        encoded += self.$n.encode_to_local(config, &mut writer).await?;
+                    )+
+
+                    Ok(encoded)
+                }
+
+                #[inline]
+                fn encode_to_vec_local(&self, config: &Config, buf: &mut Vec<u8>) -> usize {
+                    0 $(+ self.$n.encode_to_vec_local(config, buf))+
+                }
+            }
+        )+
+    }
+}
+
+impl_tuple! {
+    (0 T0)
+    (0 T0 1 T1)
+    (0 T0 1 T1 2 T2)
+    (0 T0 1 T1 2 T2 3 T3)
+    (0 T0 1 T1 2 T2 3 T3 4 T4)
+    (0 T0 1 T1 2 T2 3 T3 4 T4 5 T5)
+    (0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6)
+    (0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7)
+    (0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7 8 T8)
+    (0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7 8 T8 9 T9)
+    (0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7 8 T8 9 T9 10 T10)
+    (0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7 8 T8 9 T9 10 T10 11 T11)
+    (0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7 8 T8 9 T9 10 T10 11 T11 12 T12)
+    (0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7 8 T8 9 T9 10 T10 11 T11 12 T12 13 T13)
+    (0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7 8 T8 9 T9 10 T10 11 T11 12 T12 13 T13 14 T14)
+    (0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7 8 T8 9 T9 10 T10 11 T11 12 T12 13 T13 14 T14 15 T15)
+}
+
+impl<T: ?Sized> LocalEncode for PhantomData<T> {
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    async fn encode_to_local<W>(&self, _config: &Config, _writer: W) -> Result<usize>
+    where
+        W: Write + Unpin,
+    {
+        Ok(0)
+    }
+
+    #[inline]
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    fn encode_to_vec_local(&self, _config: &Config, _buf: &mut Vec<u8>) -> usize {
+        0
+    }
+}
@@ -0,0 +1,23 @@
+//! Type aliases for boxed futures and type-erased readers/writers (used to break recursion of nested futures)
+use alloc::boxed::Box;
+use core::{future::Future, pin::Pin};
+
+use crate::io::{Read, Write};
+
+/// Boxed `Send` future
+pub(crate) type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Type-erased `Send` reader
+pub(crate) type DynRead<'a> = dyn Read + Unpin + Send + 'a;
+
+/// Type-erased `Send` writer
+pub(crate) type DynWrite<'a> = dyn Write + Unpin + Send + 'a;
+
+/// Boxed future which is not required to be `Send`
+pub(crate) type LocalBoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+
+/// Type-erased reader which is not required to be `Send`
+pub(crate) type LocalDynRead<'a> = dyn Read + Unpin + 'a;
+
+/// Type-erased writer which is not required to be `Send`
+pub(crate) type LocalDynWrite<'a> = dyn Write + Unpin + 'a;
@@ -0,0 +1,147 @@
+use alloc::{boxed::Box, rc::Rc, sync::Arc};
+use core::{
+    cell::RefCell,
+    marker::PhantomData,
+    num::{
+        NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+        NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+    },
+};
+
+use crate::{varint::MAX_ENCODED_LEN, VarInt};
+
+/// Trait for types with a compile-time upper bound on the size of their encoded byte array
+///
+/// Implemented for bounded types (primitives, fixed arrays, tuples, `Option`, etc.) and can be derived for structs and
+/// enums (when `"derive"` feature is enabled). Unbounded types (`Vec`, `String`, maps, etc.) do not implement this
+/// trait:
+///
+/// ```compile_fail
+/// use nimble::MaxSize;
+///
+/// let buf = [0u8; <Vec<u8> as MaxSize>::MAX_SIZE];
+/// ```
+pub trait MaxSize {
+    /// Maximum size of encoded byte array of any value of this type (`Encode::size()` never exceeds this value)
+    const MAX_SIZE: usize;
+}
+
+/// Returns the larger of two sizes (usable in constant expressions)
+#[inline]
+pub const fn max(a: usize, b: usize) -> usize {
+    if a > b {
+        a
+    } else {
+        b
+    }
+}
+
+macro_rules! impl_primitive {
+    ($($type: ty),+) => {
+        $(
+            impl MaxSize for $type {
+                const MAX_SIZE: usize = core::mem::size_of::<$type>();
+            }
+        )+
+    };
+}
+
+impl_primitive!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, usize, isize, f32, f64);
+impl_primitive!(bool, char);
+impl_primitive!(
+    NonZeroU8,
+    NonZeroU16,
+    NonZeroU32,
+    NonZeroU64,
+    NonZeroU128,
+    NonZeroUsize,
+    NonZeroI8,
+    NonZeroI16,
+    NonZeroI32,
+    NonZeroI64,
+    NonZeroI128,
+    NonZeroIsize
+);
+
+impl MaxSize for VarInt {
+    const MAX_SIZE: usize = MAX_ENCODED_LEN;
+}
+
+impl MaxSize for () {
+    const MAX_SIZE: usize = 0;
+}
+
+impl<T: ?Sized> MaxSize for PhantomData<T> {
+    const MAX_SIZE: usize = 0;
+}
+
+impl<T: MaxSize> MaxSize for Option<T> {
+    const MAX_SIZE: usize = core::mem::size_of::<u8>() + T::MAX_SIZE;
+}
+
+impl<T: MaxSize, E: MaxSize> MaxSize for core::result::Result<T, E> {
+    const MAX_SIZE: usize = core::mem::size_of::<u8>() + max(T::MAX_SIZE, E::MAX_SIZE);
+}
+
+macro_rules! impl_deref {
+    ($($desc: tt)+) => {
+        impl $($desc)+ {
+            const MAX_SIZE: usize = T::MAX_SIZE;
+        }
+    };
+}
+
+impl_deref!(<T: MaxSize + ?Sized> MaxSize for &T);
+impl_deref!(<T: MaxSize + ?Sized> MaxSize for &mut T);
+impl_deref!(<T: MaxSize + ?Sized> MaxSize for Box<T>);
+impl_deref!(<T: MaxSize + ?Sized> MaxSize for Rc<T>);
+impl_deref!(<T: MaxSize + ?Sized> MaxSize for Arc<T>);
+impl_deref!(<T: MaxSize + ?Sized> MaxSize for RefCell<T>);
+
+macro_rules! impl_fixed_arr {
+    ($($len: expr),+) => {
+        $(
+            impl<T: MaxSize> MaxSize for [T; $len] {
+                const MAX_SIZE: usize = $len * T::MAX_SIZE;
+            }
+        )+
+    };
+}
+
+impl_fixed_arr!(
+    1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26,
+    27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50,
+    51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63, 64, 128, 256, 512, 1024
+);
+
+macro_rules! impl_tuple {
+    ($(($($name:tt)+))+) => {
+        $(
+            impl<$($name),+> MaxSize for ($($name,)+)
+            where
+                $($name: MaxSize,)+
+            {
+                const MAX_SIZE: usize = 0 $(+ $name::MAX_SIZE)+;
+            }
+        )+
+    }
+}
+
+impl_tuple! {
+    (T0)
+    (T0 T1)
+    (T0 T1 T2)
+    (T0 T1 T2 T3)
+    (T0 T1 T2 T3 T4)
+    (T0 T1 T2 T3 T4 T5)
+    (T0 T1 T2 T3 T4 T5 T6)
+    (T0 T1 T2 T3 T4 T5 T6 T7)
+    (T0 T1 T2 T3 T4 T5 T6 T7 T8)
+    (T0 T1 T2 T3 T4 T5 T6 T7 T8 T9)
+    (T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10)
+    (T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11)
+    (T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12)
+    (T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13)
+    (T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13 T14)
+    (T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11 T12 T13 T14 T15)
+}
@@ -0,0 +1,124 @@
+use core::{borrow::Borrow, convert::TryFrom, marker::PhantomData};
+
+#[cfg(feature = "futures")]
+use futures_util::stream::{Stream, StreamExt};
+
+use crate::{
+    io::{Write, WriteExt},
+    Config, Encode, Error, Result, VarInt,
+};
+
+/// Encoder which writes a sequence element by element in the same format as `Vec<T>` (without collecting the elements)
+///
+/// `VarInt` length of the sequence is written when the encoder is created and elements are written to writer as they
+/// are encoded. Encoding more elements than the declared length (or finishing the sequence with fewer elements) returns
+/// [`Error::LengthMismatch`](enum.Error.html#variant.LengthMismatch).
+#[derive(Debug)]
+pub struct SeqEncoder<T: ?Sized, W> {
+    writer: W,
+    config: Config,
+    len: usize,
+    encoded: usize,
+    written: usize,
+    _marker: PhantomData<fn(&T)>,
+}
+
+impl<T, W> SeqEncoder<T, W>
+where
+    T: Encode + ?Sized,
+    W: Write + Unpin + Send,
+{
+    /// Creates a new encoder for a sequence of `len` elements using default configuration and writes its length
+    #[inline]
+    pub async fn new(len: usize, writer: W) -> Result<Self> {
+        Self::with_config(Config::default(), len, writer).await
+    }
+
+    /// Creates a new encoder for a sequence of `len` elements using given configuration and writes its length
+    pub async fn with_config(config: Config, len: usize, mut writer: W) -> Result<Self> {
+        let written = VarInt::try_from(len)?
+            .encode_to(&config, &mut writer)
+            .await?;
+
+        Ok(Self {
+            writer,
+            config,
+            len,
+            encoded: 0,
+            written,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Returns the number of elements which are yet to be encoded
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.len - self.encoded
+    }
+
+    /// Writes the next element of the sequence and returns the number of bytes written
+    pub async fn encode(&mut self, value: &T) -> Result<usize> {
+        if self.encoded == self.len {
+            return Err(Error::LengthMismatch {
+                expected: self.len,
+                actual: self.len + 1,
+            });
+        }
+
+        let written = value.encode_to(&self.config, &mut self.writer).await?;
+
+        self.encoded += 1;
+        self.written += written;
+
+        Ok(written)
+    }
+
+    /// Writes all the elements of an iterator and returns the number of bytes written
+    pub async fn encode_iter<I>(&mut self, iter: I) -> Result<usize>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<T>,
+    {
+        let mut written = 0;
+
+        for value in iter {
+            written += self.encode(value.borrow()).await?;
+        }
+
+        Ok(written)
+    }
+
+    #[cfg(feature = "futures")]
+    /// Writes all the elements of a `futures`' `Stream` and returns the number of bytes written
+    pub async fn encode_stream<S>(&mut self, mut stream: S) -> Result<usize>
+    where
+        S: Stream + Unpin,
+        S::Item: Borrow<T>,
+    {
+        let mut written = 0;
+
+        while let Some(value) = stream.next().await {
+            written += self.encode(value.borrow()).await?;
+        }
+
+        Ok(written)
+    }
+
+    /// Finishes the sequence and returns the total number of bytes written (including length)
+    ///
+    /// Writer is flushed if [`Config::flush`](struct.Config.html#method.flush) is `true`.
+    pub async fn finish(mut self) -> Result<usize> {
+        if self.encoded != self.len {
+            return Err(Error::LengthMismatch {
+                expected: self.len,
+                actual: self.encoded,
+            });
+        }
+
+        if self.config.flush() {
+            self.writer.flush().await?;
+        }
+
+        Ok(self.written)
+    }
+}
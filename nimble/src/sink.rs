@@ -0,0 +1,111 @@
+use alloc::vec::Vec;
+use core::{
+    marker::PhantomData,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+
+use futures_util::sink;
+
+use crate::{io::Write, Config, Encode, Error, Result};
+
+/// Adapter implementing `futures`' `Sink` for writing encoded values to a [`Write`](io/trait.Write.html) type
+///
+/// Each value is encoded into an internal buffer (which is reused for all the values) and written to writer before
+/// accepting the next value. Flushing (or closing) the sink writes any buffered bytes and flushes (or closes) writer.
+#[derive(Debug)]
+pub struct Sink<T, W> {
+    writer: W,
+    config: Config,
+    buf: Vec<u8>,
+    written: usize,
+    _marker: PhantomData<fn(T)>,
+}
+
+impl<T, W> Sink<T, W> {
+    /// Creates a new sink using default configuration
+    #[inline]
+    pub fn new(writer: W) -> Self {
+        Self::with_config(Config::default(), writer)
+    }
+
+    /// Creates a new sink using given configuration
+    #[inline]
+    pub fn with_config(config: Config, writer: W) -> Self {
+        Self {
+            writer,
+            config,
+            buf: Vec::new(),
+            written: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns a reference to the inner writer
+    #[inline]
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Returns a mutable reference to the inner writer
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Consumes the sink and returns the inner writer (buffered bytes which are not yet written are discarded)
+    #[inline]
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<T, W: Write + Unpin> Sink<T, W> {
+    /// Writes all the buffered bytes to writer
+    fn poll_write_buf(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        while self.written < self.buf.len() {
+            let written =
+                ready!(Pin::new(&mut self.writer).poll_write(cx, &self.buf[self.written..]))?;
+
+            if written == 0 {
+                return Poll::Ready(Err(Error::WriteZero));
+            }
+
+            self.written += written;
+        }
+
+        self.buf.clear();
+        self.written = 0;
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<T: Encode, W: Write + Unpin> sink::Sink<T> for Sink<T, W> {
+    type Error = Error;
+
+    #[inline]
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.get_mut().poll_write_buf(cx)
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: T) -> Result<()> {
+        let this = self.get_mut();
+        item.encode_to_vec(&this.config, &mut this.buf);
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let this = self.get_mut();
+
+        ready!(this.poll_write_buf(cx))?;
+        Pin::new(&mut this.writer).poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let this = self.get_mut();
+
+        ready!(this.poll_write_buf(cx))?;
+        Pin::new(&mut this.writer).poll_close(cx)
+    }
+}
@@ -0,0 +1,141 @@
+//! Adapters for decoding values as `futures`' `Stream`s
+use core::{
+    convert::TryFrom,
+    future::poll_fn,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_util::stream::{self, Stream};
+
+use crate::{io::Read, Config, Decode, Result, VarInt};
+
+/// Returns a stream of values decoded one after another from reader
+///
+/// Stream ends when reader reaches end of source at a value boundary. If reader reaches end of source in the middle of a
+/// value, stream yields [`Error::UnexpectedEof`](../enum.Error.html#variant.UnexpectedEof). Stream ends after yielding
+/// the first error.
+pub fn decode_stream<T, R>(config: Config, reader: R) -> impl Stream<Item = Result<T>> + Send
+where
+    T: Decode + Send,
+    R: Read + Unpin + Send,
+{
+    stream::unfold(Some((config, reader)), |state| async move {
+        let (config, mut reader) = state?;
+
+        // Read first byte of next value to check if reader is at a value boundary
+        let mut first = [0u8; 1];
+        let read = poll_fn(|cx| Pin::new(&mut reader).poll_read(cx, &mut first)).await;
+
+        let result = match read {
+            Ok(0) => return None,
+            Ok(_) => {
+                let mut chained = Chain {
+                    first: &first[..],
+                    second: &mut reader,
+                };
+
+                T::decode_from(&config, &mut chained).await
+            }
+            Err(err) => Err(err),
+        };
+
+        match result {
+            Ok(value) => Some((Ok(value), Some((config, reader)))),
+            Err(err) => Some((Err(err), None)),
+        }
+    })
+}
+
+/// Returns a stream of elements of an encoded sequence (for example, `Vec<T>`) decoded one at a time from reader
+///
+/// `VarInt` length prefix is read when stream is first polled and stream ends after yielding that many elements, so that
+/// large sequences can be processed without holding all the elements in memory. Stream ends after yielding the first
+/// error.
+pub fn decode_seq_stream<T, R>(config: Config, reader: R) -> impl Stream<Item = Result<T>> + Send
+where
+    T: Decode + Send,
+    R: Read + Unpin + Send,
+{
+    stream::unfold(Some((config, reader, None)), |state| async move {
+        let (config, mut reader, remaining) = state?;
+
+        let remaining = match remaining {
+            Some(remaining) => remaining,
+            None => {
+                let len = VarInt::decode_from(&config, &mut reader)
+                    .await
+                    .and_then(usize::try_from);
+
+                match len {
+                    Ok(len) => len,
+                    Err(err) => return Some((Err(err), None)),
+                }
+            }
+        };
+
+        if remaining == 0 {
+            return None;
+        }
+
+        match T::decode_from(&config, &mut reader).await {
+            Ok(value) => Some((Ok(value), Some((config, reader, Some(remaining - 1))))),
+            Err(err) => Some((Err(err), None)),
+        }
+    })
+}
+
+/// Returns a stream of elements of a chunked sequence (for example, [`Chunked<T>`](../struct.Chunked.html)) decoded one
+/// at a time from reader
+///
+/// Stream ends after yielding the elements of all the chunks up to the terminating empty chunk. Stream ends after
+/// yielding the first error.
+pub fn decode_chunked_stream<T, R>(
+    config: Config,
+    reader: R,
+) -> impl Stream<Item = Result<T>> + Send
+where
+    T: Decode + Send,
+    R: Read + Unpin + Send,
+{
+    stream::unfold(Some((config, reader, 0)), |state| async move {
+        let (config, mut reader, mut remaining) = state?;
+
+        if remaining == 0 {
+            let len = VarInt::decode_from(&config, &mut reader)
+                .await
+                .and_then(usize::try_from);
+
+            match len {
+                Ok(0) => return None,
+                Ok(len) => remaining = len,
+                Err(err) => return Some((Err(err), None)),
+            }
+        }
+
+        match T::decode_from(&config, &mut reader).await {
+            Ok(value) => Some((Ok(value), Some((config, reader, remaining - 1)))),
+            Err(err) => Some((Err(err), None)),
+        }
+    })
+}
+
+/// Reader which reads all the bytes from `first` and then from `second`
+struct Chain<'a, R> {
+    first: &'a [u8],
+    second: R,
+}
+
+impl<R: Read + Unpin> Read for Chain<'_, R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<Result<usize>> {
+        if self.first.is_empty() {
+            Pin::new(&mut self.second).poll_read(cx, buf)
+        } else {
+            Pin::new(&mut self.first).poll_read(cx, buf)
+        }
+    }
+}
@@ -1,13 +1,44 @@
 //! Utilities for encoding/decoding VarInt
+use alloc::vec::Vec;
 use core::{convert::TryFrom, fmt};
 
-use async_trait::async_trait;
-
 use crate::{
-    io::{Read, Write},
+    io::{Read, Write, WriteExt},
     Config, Decode, Encode, Error, Result,
 };
 
+/// Maximum number of bytes in an encoded `VarInt` (`ceil(128 / 7)`)
+pub(crate) const MAX_ENCODED_LEN: usize = 19;
+
+/// Returns the number of bytes in encoded `VarInt` of `num`
+#[inline]
+pub const fn encoded_len(mut num: u128) -> usize {
+    let mut len = 1;
+
+    while num >= 0b1000_0000 {
+        len += 1;
+        num >>= 7;
+    }
+
+    len
+}
+
+/// Adds lower 7 bits of `byte` to `num` (shifted by `shift_by` bits) and returns `true` if more bytes follow
+///
+/// Returns [`Error::InvalidVarInt`](../enum.Error.html#variant.InvalidVarInt) if the bits do not fit in `u128`.
+#[inline]
+fn add_byte(num: &mut u128, shift_by: u32, byte: u8) -> Result<bool> {
+    let bits = u128::from(byte & 0b0111_1111);
+
+    if shift_by >= u128::BITS || bits.checked_shr(u128::BITS - shift_by).unwrap_or(0) != 0 {
+        return Err(Error::InvalidVarInt);
+    }
+
+    *num |= bits << shift_by;
+
+    Ok(byte & 0b1000_0000 != 0)
+}
+
 /// Base 128 VarInt ([Reference](https://developers.google.com/protocol-buffers/docs/encoding#varints))
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 pub struct VarInt(u128);
@@ -18,60 +49,90 @@ impl fmt::Display for VarInt {
     }
 }
 
-#[async_trait]
-impl Encode for VarInt {
-    fn size(&self) -> usize {
-        if self.0 == 0 {
-            return 1;
-        }
-
-        let mut counter = 0;
+impl VarInt {
+    /// Encodes value in a buffer on stack (so that it can be written to a writer at once) and returns the buffer along
+    /// with the number of encoded bytes
+    pub(crate) fn to_array(self) -> ([u8; MAX_ENCODED_LEN], usize) {
+        let mut bytes = [0u8; MAX_ENCODED_LEN];
         let mut num = self.0;
+        let mut encoded = 0;
+
+        while num >= 0b1000_0000 {
+            bytes[encoded] = (num & 0b0111_1111) as u8 | 0b1000_0000;
+            encoded += 1;
 
-        while num > 0 {
-            counter += 1;
             num >>= 7;
         }
 
-        counter
+        bytes[encoded] = num as u8;
+        encoded += 1;
+
+        (bytes, encoded)
     }
+}
 
-    async fn encode_to<W>(&self, config: &Config, mut writer: W) -> Result<usize>
+impl Encode for VarInt {
+    #[inline]
+    fn size(&self) -> usize {
+        encoded_len(self.0)
+    }
+
+    async fn encode_to<W>(&self, _config: &Config, mut writer: W) -> Result<usize>
     where
         W: Write + Unpin + Send,
     {
+        let (bytes, encoded) = self.to_array();
+        writer.write_all(&bytes[..encoded]).await?;
+
+        Ok(encoded)
+    }
+
+    fn encode_to_vec(&self, config: &Config, buf: &mut Vec<u8>) -> usize {
         let mut num = self.0;
         let mut encoded = 0;
 
         while num >= 0b1000_0000 {
             let byte: u8 = (num & 0b0111_1111) as u8 | 0b1000_0000;
-            encoded += byte.encode_to(config, &mut writer).await?;
+            encoded += byte.encode_to_vec(config, buf);
 
             num >>= 7;
         }
 
-        encoded += (num as u8).encode_to(config, &mut writer).await?;
+        encoded += (num as u8).encode_to_vec(config, buf);
 
-        Ok(encoded)
+        encoded
     }
 }
 
-#[async_trait]
 impl Decode for VarInt {
     async fn decode_from<R>(config: &Config, mut reader: R) -> Result<Self>
     where
         R: Read + Unpin + Send,
     {
         let mut num: u128 = 0;
-        let mut shift_by: u8 = 0;
+        let mut shift_by: u32 = 0;
 
         loop {
             let byte = u8::decode_from(config, &mut reader).await?;
-            num |= ((byte & 0b0111_1111) as u128) << shift_by;
 
-            let has_next_byte = byte & 0b1000_0000 != 0;
+            if add_byte(&mut num, shift_by, byte)? {
+                shift_by += 7;
+            } else {
+                break;
+            }
+        }
+
+        Ok(VarInt(num))
+    }
+
+    fn decode_from_slice(config: &Config, bytes: &mut &[u8]) -> Result<Self> {
+        let mut num: u128 = 0;
+        let mut shift_by: u32 = 0;
+
+        loop {
+            let byte = u8::decode_from_slice(config, bytes)?;
 
-            if has_next_byte {
+            if add_byte(&mut num, shift_by, byte)? {
                 shift_by += 7;
             } else {
                 break;